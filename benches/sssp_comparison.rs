@@ -1,5 +1,21 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use sssp_algos::{dijkstra, bfs, generate_random_graph};
+use sssp_algos::{dijkstra, bfs, generate_random_graph_seeded, seed_from_env, SEED_ENV_VAR};
+use sssp_algos::algorithms::{bmssp, dijkstra_with_workspace, ContractionHierarchy, QueryWorkspace};
+use sssp_algos::generators::{
+    edges_for_average_degree, generate_chain_with_shortcuts, generate_decrease_key_graph, generate_gnm_graph_seeded,
+    generate_layered_graph_seeded, generate_tie_grid, WeightDistribution,
+};
+use std::sync::OnceLock;
+
+// One seed per bench run, printed once so the graphs can be reproduced
+fn seed() -> u64 {
+    static SEED: OnceLock<u64> = OnceLock::new();
+    *SEED.get_or_init(|| {
+        let seed = seed_from_env();
+        println!("Graph seed: {} (replay with {}={})", seed, SEED_ENV_VAR, seed);
+        seed
+    })
+}
 
 fn benchmark_algorithms(c: &mut Criterion) {
     let mut group = c.benchmark_group("SSSP Algorithms");
@@ -9,7 +25,7 @@ fn benchmark_algorithms(c: &mut Criterion) {
     let edge_density = 0.3;
     
     for size in sizes.iter() {
-        let graph = generate_random_graph(*size, edge_density);
+        let graph = generate_random_graph_seeded(*size, edge_density, seed());
         let start_node = 0;
         
         group.bench_with_input(
@@ -36,5 +52,136 @@ fn benchmark_algorithms(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, benchmark_algorithms);
+fn benchmark_point_to_point(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Point-to-point Queries");
+    
+    let sizes = [100, 500, 1000];
+    let edge_density = 0.01;
+    
+    for size in sizes.iter() {
+        let graph = generate_random_graph_seeded(*size, edge_density, seed());
+        let hierarchy = ContractionHierarchy::build(&graph);
+        let (source, target) = (0, size - 1);
+        
+        group.bench_with_input(
+            BenchmarkId::new("Dijkstra", size),
+            size,
+            |b, _| {
+                b.iter(|| {
+                    dijkstra(black_box(&graph), black_box(source))[&target]
+                })
+            },
+        );
+        
+        group.bench_with_input(
+            BenchmarkId::new("Contraction Hierarchy", size),
+            size,
+            |b, _| {
+                b.iter(|| {
+                    hierarchy.distance(black_box(source), black_box(target))
+                })
+            },
+        );
+    }
+    
+    group.finish();
+}
+
+fn benchmark_repeated_queries(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Repeated Queries");
+    
+    // Many queries on one graph: a fresh distance map per call vs. a reused workspace
+    let graph = generate_random_graph_seeded(2000, 0.001, seed());
+    let sources: Vec<usize> = (0..2000).step_by(20).collect();
+    let mut workspace = QueryWorkspace::new(&graph);
+    
+    group.bench_function("Dijkstra", |b| {
+        b.iter(|| {
+            for &source in &sources {
+                black_box(dijkstra(black_box(&graph), source));
+            }
+        })
+    });
+    
+    group.bench_function("Dijkstra with workspace", |b| {
+        b.iter(|| {
+            for &source in &sources {
+                dijkstra_with_workspace(black_box(&graph), source, &mut workspace);
+                black_box(workspace.distance(0));
+            }
+        })
+    });
+    
+    group.finish();
+}
+
+fn benchmark_sparse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Sparse G(n, m), average degree 3");
+    group.sample_size(10);
+    
+    for size in [10_000, 100_000].iter() {
+        let edges = edges_for_average_degree(*size, 3.0, true);
+        let graph = generate_gnm_graph_seeded(*size, edges, true, &WeightDistribution::default(), seed());
+        
+        group.bench_with_input(
+            BenchmarkId::new("Dijkstra", size),
+            size,
+            |b, _| {
+                b.iter(|| {
+                    dijkstra(black_box(&graph), black_box(0))
+                })
+            },
+        );
+        
+        group.bench_with_input(
+            BenchmarkId::new("BMSSP", size),
+            size,
+            |b, _| {
+                b.iter(|| {
+                    bmssp(black_box(&graph), black_box(0))
+                })
+            },
+        );
+    }
+    
+    group.finish();
+}
+
+fn benchmark_adversarial(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Adversarial Families");
+    group.sample_size(10);
+    
+    let families = [
+        ("decrease-key", generate_decrease_key_graph(5000, 32)),
+        ("chain with shortcuts", generate_chain_with_shortcuts(50_000, 8)),
+        ("layered", generate_layered_graph_seeded(50, 40, &WeightDistribution::default(), seed())),
+        ("tie grid", generate_tie_grid(300, 300)),
+    ];
+    
+    for (name, graph) in families.iter() {
+        group.bench_with_input(
+            BenchmarkId::new("Dijkstra", name),
+            name,
+            |b, _| {
+                b.iter(|| {
+                    dijkstra(black_box(graph), black_box(0))
+                })
+            },
+        );
+        
+        group.bench_with_input(
+            BenchmarkId::new("BMSSP", name),
+            name,
+            |b, _| {
+                b.iter(|| {
+                    bmssp(black_box(graph), black_box(0))
+                })
+            },
+        );
+    }
+    
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_algorithms, benchmark_point_to_point, benchmark_repeated_queries, benchmark_sparse, benchmark_adversarial);
 criterion_main!(benches);
@@ -0,0 +1,227 @@
+// ALT: A* with Landmarks and the Triangle inequality (Goldberg & Harrelson, 2005)
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use crate::algorithms::astar::{astar, Heuristic};
+use crate::algorithms::dijkstra::{dijkstra, dijkstra_tree};
+use crate::graph::Graph;
+
+/// How landmarks are picked during preprocessing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LandmarkStrategy {
+    /// Uniformly random distinct nodes
+    Random,
+    /// Greedily pick the node farthest from the landmarks chosen so far
+    Farthest,
+    /// Grow a shortest-path tree from a random root and descend into the
+    /// subtree whose nodes currently have the worst lower bounds
+    Avoid,
+}
+
+/// Precomputed landmark distance tables.
+///
+/// Serializable so the preprocessing can be saved once and reused across runs
+/// (see [`Landmarks::save`] and [`Landmarks::load`]). Only reachable entries are
+/// stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Landmarks {
+    landmarks: Vec<usize>,
+    /// `from_landmark[i][v]` = d(landmark i, v)
+    from_landmark: Vec<HashMap<usize, usize>>,
+    /// `to_landmark[i][v]` = d(v, landmark i)
+    to_landmark: Vec<HashMap<usize, usize>>,
+}
+
+impl Landmarks {
+    /// Select `count` landmarks with `strategy` and compute their distance tables
+    pub fn select(graph: &Graph, count: usize, strategy: LandmarkStrategy) -> Self {
+        Self::select_with_rng(graph, count, strategy, &mut rand::thread_rng())
+    }
+
+    /// [`Landmarks::select`] with a fixed seed
+    pub fn select_seeded(graph: &Graph, count: usize, strategy: LandmarkStrategy, seed: u64) -> Self {
+        Self::select_with_rng(graph, count, strategy, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// [`Landmarks::select`] drawing from `rng`
+    pub fn select_with_rng<R: Rng + ?Sized>(graph: &Graph, count: usize, strategy: LandmarkStrategy, rng: &mut R) -> Self {
+        let reverse = graph.reversed();
+        let mut landmarks = Landmarks {
+            landmarks: Vec::new(),
+            from_landmark: Vec::new(),
+            to_landmark: Vec::new(),
+        };
+        let count = count.min(graph.node_count());
+
+        if strategy == LandmarkStrategy::Random {
+            let chosen: Vec<usize> = graph.nodes().choose_multiple(rng, count).copied().collect();
+            for landmark in chosen {
+                landmarks.push(graph, &reverse, landmark);
+            }
+            return landmarks;
+        }
+
+        while landmarks.landmarks.len() < count {
+            let next = match strategy {
+                LandmarkStrategy::Farthest => landmarks.farthest_candidate(graph, rng),
+                _ => landmarks.avoid_candidate(graph, rng),
+            };
+            match next {
+                Some(landmark) => landmarks.push(graph, &reverse, landmark),
+                None => break,
+            }
+        }
+
+        landmarks
+    }
+
+    pub fn landmarks(&self) -> &[usize] {
+        &self.landmarks
+    }
+
+    /// Lower bound on d(from, to) from the triangle inequality over all landmarks
+    pub fn lower_bound(&self, from: usize, to: usize) -> usize {
+        let mut bound = 0;
+
+        for i in 0..self.landmarks.len() {
+            // d(L, to) - d(L, from) <= d(from, to)
+            if let (Some(&l_from), Some(&l_to)) = (self.from_landmark[i].get(&from), self.from_landmark[i].get(&to)) {
+                bound = bound.max(l_to.saturating_sub(l_from));
+            }
+            // d(from, L) - d(to, L) <= d(from, to)
+            if let (Some(&from_l), Some(&to_l)) = (self.to_landmark[i].get(&from), self.to_landmark[i].get(&to)) {
+                bound = bound.max(from_l.saturating_sub(to_l));
+            }
+        }
+
+        bound
+    }
+
+    /// A* heuristic towards `target` using the landmark lower bounds
+    pub fn heuristic(&self, target: usize) -> AltHeuristic<'_> {
+        AltHeuristic { landmarks: self, target }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// Write the preprocessing to `path` as JSON
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_json()?)
+    }
+
+    /// Read preprocessing previously written by [`Landmarks::save`]
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::from_json(&fs::read_to_string(path)?)?)
+    }
+
+    fn push(&mut self, graph: &Graph, reverse: &Graph, landmark: usize) {
+        let reachable = |distances: HashMap<usize, usize>| -> HashMap<usize, usize> {
+            distances.into_iter().filter(|&(_, d)| d != usize::MAX).collect()
+        };
+
+        self.landmarks.push(landmark);
+        self.from_landmark.push(reachable(dijkstra(graph, landmark)));
+        self.to_landmark.push(reachable(dijkstra(reverse, landmark)));
+    }
+
+    fn farthest_candidate<R: Rng + ?Sized>(&self, graph: &Graph, rng: &mut R) -> Option<usize> {
+        let chosen: HashSet<usize> = self.landmarks.iter().copied().collect();
+
+        if self.landmarks.is_empty() {
+            // Start from the node farthest away from an arbitrary node
+            let start = *graph.nodes().choose(rng)?;
+            let distances = dijkstra(graph, start);
+            return graph.nodes().iter()
+                .copied()
+                .filter(|node| distances[node] != usize::MAX)
+                .max_by_key(|node| distances[node]);
+        }
+
+        // Maximize the distance to the closest landmark already chosen
+        graph.nodes().iter()
+            .copied()
+            .filter(|node| !chosen.contains(node))
+            .max_by_key(|node| {
+                self.from_landmark.iter()
+                    .filter_map(|distances| distances.get(node))
+                    .min()
+                    .copied()
+                    .unwrap_or(0)
+            })
+    }
+
+    fn avoid_candidate<R: Rng + ?Sized>(&self, graph: &Graph, rng: &mut R) -> Option<usize> {
+        let chosen: HashSet<usize> = self.landmarks.iter().copied().collect();
+        let candidates: Vec<usize> = graph.nodes().iter().copied().filter(|node| !chosen.contains(node)).collect();
+        let root = *candidates.choose(rng)?;
+
+        let tree = dijkstra_tree(graph, root);
+        let children = tree.children();
+
+        // Post-order over the tree so every child is sized before its parent
+        let mut order = Vec::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            order.push(node);
+            if let Some(kids) = children.get(&node) {
+                stack.extend(kids);
+            }
+        }
+
+        // size(v) = sum of (d(r, u) - lower_bound(r, u)) over the subtree of v,
+        // or 0 if that subtree already contains a landmark
+        let mut size: HashMap<usize, usize> = HashMap::new();
+        let mut covered: HashSet<usize> = HashSet::new();
+        for &node in order.iter().rev() {
+            let kids = children.get(&node).map_or(&[][..], |v| v.as_slice());
+            if chosen.contains(&node) || kids.iter().any(|kid| covered.contains(kid)) {
+                covered.insert(node);
+                size.insert(node, 0);
+                continue;
+            }
+            let own = tree.distance(node) - self.lower_bound(root, node).min(tree.distance(node));
+            let total = kids.iter().fold(own, |sum, kid| sum.saturating_add(size[kid]));
+            size.insert(node, total);
+        }
+
+        // Walk down through the heaviest child until reaching a leaf. Children
+        // come in hash order, so ties go to the larger id to stay reproducible.
+        let mut current = root;
+        while let Some(next) = children.get(&current)
+            .and_then(|kids| kids.iter().copied().filter(|kid| size[kid] > 0).max_by_key(|&kid| (size[&kid], kid)))
+        {
+            current = next;
+        }
+
+        Some(current)
+    }
+}
+
+/// Heuristic produced by [`Landmarks::heuristic`]
+pub struct AltHeuristic<'a> {
+    landmarks: &'a Landmarks,
+    target: usize,
+}
+
+impl Heuristic for AltHeuristic<'_> {
+    fn estimate(&self, node: usize) -> usize {
+        self.landmarks.lower_bound(node, self.target)
+    }
+}
+
+/// Point-to-point query: A* guided by the landmark lower bounds
+pub fn alt_query(graph: &Graph, landmarks: &Landmarks, source: usize, target: usize) -> Option<(usize, Vec<usize>)> {
+    astar(graph, source, target, &landmarks.heuristic(target))
+}
@@ -0,0 +1,192 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::cmp::Ordering;
+use crate::algorithms::dijkstra;
+use crate::graph::Graph;
+
+/// Lower bound on the remaining distance from a node to the search target
+pub trait Heuristic {
+    fn estimate(&self, node: usize) -> usize;
+}
+
+impl<F: Fn(usize) -> usize> Heuristic for F {
+    fn estimate(&self, node: usize) -> usize {
+        self(node)
+    }
+}
+
+/// Straight-line distance to the target, multiplied by `scale`.
+///
+/// Admissible as long as every edge weight is at least `scale` times the
+/// Euclidean length between its endpoints. Nodes without a position estimate 0.
+pub struct EuclideanHeuristic<'a> {
+    graph: &'a Graph,
+    target: (f64, f64),
+    scale: f64,
+}
+
+impl<'a> EuclideanHeuristic<'a> {
+    /// Returns `None` if `target` has no position in `graph`
+    pub fn new(graph: &'a Graph, target: usize, scale: f64) -> Option<Self> {
+        Some(EuclideanHeuristic { graph, target: graph.position(target)?, scale })
+    }
+}
+
+impl Heuristic for EuclideanHeuristic<'_> {
+    fn estimate(&self, node: usize) -> usize {
+        match self.graph.position(node) {
+            Some((x, y)) => {
+                let (dx, dy) = (x - self.target.0, y - self.target.1);
+                ((dx * dx + dy * dy).sqrt() * self.scale).floor() as usize
+            }
+            None => 0,
+        }
+    }
+}
+
+/// Manhattan (L1) distance to the target, multiplied by `scale`.
+///
+/// Suited to grid graphs without diagonal moves. Nodes without a position estimate 0.
+pub struct ManhattanHeuristic<'a> {
+    graph: &'a Graph,
+    target: (f64, f64),
+    scale: f64,
+}
+
+impl<'a> ManhattanHeuristic<'a> {
+    /// Returns `None` if `target` has no position in `graph`
+    pub fn new(graph: &'a Graph, target: usize, scale: f64) -> Option<Self> {
+        Some(ManhattanHeuristic { graph, target: graph.position(target)?, scale })
+    }
+}
+
+impl Heuristic for ManhattanHeuristic<'_> {
+    fn estimate(&self, node: usize) -> usize {
+        match self.graph.position(node) {
+            Some((x, y)) => {
+                let distance = (x - self.target.0).abs() + (y - self.target.1).abs();
+                (distance * self.scale).floor() as usize
+            }
+            None => 0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct State {
+    estimate: usize,
+    cost: usize,
+    position: usize,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+            .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A* search from `start` to `target`.
+///
+/// Nodes are reopened when a cheaper path to them is found, so the result is
+/// optimal for any admissible heuristic, even an inconsistent one.
+/// Returns the distance and the path, or `None` if `target` is unreachable.
+pub fn astar<H: Heuristic + ?Sized>(
+    graph: &Graph,
+    start: usize,
+    target: usize,
+    heuristic: &H,
+) -> Option<(usize, Vec<usize>)> {
+    let mut costs = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    costs.insert(start, 0);
+    heap.push(State { estimate: heuristic.estimate(start), cost: 0, position: start });
+
+    while let Some(State { cost, position, .. }) = heap.pop() {
+        if cost > *costs.get(&position).unwrap_or(&usize::MAX) {
+            continue;
+        }
+
+        if position == target {
+            let mut path = vec![target];
+            let mut current = target;
+            while let Some(&previous) = predecessors.get(&current) {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+
+        for &(neighbor, weight) in graph.neighbors(position) {
+            let next_cost = cost.saturating_add(weight);
+
+            if next_cost < *costs.get(&neighbor).unwrap_or(&usize::MAX) {
+                costs.insert(neighbor, next_cost);
+                predecessors.insert(neighbor, position);
+                heap.push(State {
+                    estimate: next_cost.saturating_add(heuristic.estimate(neighbor)),
+                    cost: next_cost,
+                    position: neighbor,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Problems found by [`check_heuristic`]
+#[derive(Debug, Default)]
+pub struct HeuristicReport {
+    /// `(node, estimate, true distance)` for nodes where the heuristic overestimates
+    pub inadmissible: Vec<(usize, usize, usize)>,
+    /// `(from, to, weight)` for edges where `h(from) > weight + h(to)`
+    pub inconsistent: Vec<(usize, usize, usize)>,
+}
+
+impl HeuristicReport {
+    pub fn is_admissible(&self) -> bool {
+        self.inadmissible.is_empty()
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistent.is_empty()
+    }
+}
+
+/// Check `heuristic` against the true distances to `target`.
+///
+/// An inadmissible heuristic can make [`astar`] return a suboptimal path; an
+/// admissible but inconsistent one is still correct but reopens nodes.
+pub fn check_heuristic<H: Heuristic + ?Sized>(graph: &Graph, target: usize, heuristic: &H) -> HeuristicReport {
+    let mut report = HeuristicReport::default();
+
+    // Distances *to* target are distances from target in the reverse graph
+    let to_target = dijkstra(&graph.reversed(), target);
+
+    for &node in graph.nodes() {
+        let estimate = heuristic.estimate(node);
+        let distance = *to_target.get(&node).unwrap_or(&usize::MAX);
+
+        if distance != usize::MAX && estimate > distance {
+            report.inadmissible.push((node, estimate, distance));
+        }
+
+        for &(neighbor, weight) in graph.neighbors(node) {
+            if estimate > weight.saturating_add(heuristic.estimate(neighbor)) {
+                report.inconsistent.push((node, neighbor, weight));
+            }
+        }
+    }
+
+    report
+}
@@ -0,0 +1,108 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use serde::{Deserialize, Serialize};
+use crate::algorithms::workspace::{dijkstra_slots, Scratch, SlotGraph};
+use crate::graph::Graph;
+
+/// Distances from a list of sources to a list of targets, stored row-major
+/// (one row per source, `usize::MAX` for unreachable targets)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DistanceMatrix {
+    sources: Vec<usize>,
+    targets: Vec<usize>,
+    distances: Vec<usize>,
+}
+
+impl DistanceMatrix {
+    pub fn sources(&self) -> &[usize] {
+        &self.sources
+    }
+
+    pub fn targets(&self) -> &[usize] {
+        &self.targets
+    }
+
+    /// Distances from the `i`-th source, in target order
+    pub fn row(&self, i: usize) -> &[usize] {
+        let width = self.targets.len();
+        &self.distances[i * width..(i + 1) * width]
+    }
+
+    /// Distance between two node ids, `None` if either is not part of the matrix
+    pub fn distance(&self, source: usize, target: usize) -> Option<usize> {
+        let i = self.sources.iter().position(|&s| s == source)?;
+        let j = self.targets.iter().position(|&t| t == target)?;
+        Some(self.row(i)[j])
+    }
+}
+
+fn thread_count(threads: usize, jobs: usize) -> usize {
+    let threads = if threads == 0 {
+        thread::available_parallelism().map_or(1, |n| n.get())
+    } else {
+        threads
+    };
+    threads.clamp(1, jobs.max(1))
+}
+
+/// Run Dijkstra from every source on `threads` worker threads (0 = one per
+/// core) and hand each result row to `on_row` on the calling thread as soon as
+/// it is ready. Rows arrive in completion order, tagged with the source's
+/// index in `sources`. `targets` defaults to every node of the graph.
+///
+/// The graph is renumbered to dense slots once and shared by all workers;
+/// each worker only owns its distances, timestamps and heap, so memory grows with
+/// `threads · n` rather than with `threads` copies of the graph, and
+/// per-query allocation is limited to the result row.
+pub fn batch_for_each<F>(graph: &Graph, sources: &[usize], targets: Option<&[usize]>, threads: usize, mut on_row: F)
+where
+    F: FnMut(usize, Vec<usize>),
+{
+    let slots = SlotGraph::new(graph);
+    let target_slots: Vec<Option<usize>> = targets.unwrap_or(graph.nodes()).iter().map(|&target| slots.slot(target)).collect();
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..thread_count(threads, sources.len()) {
+            let sender = sender.clone();
+            let (next, slots, target_slots) = (&next, &slots, &target_slots);
+            scope.spawn(move || {
+                let mut scratch = Scratch::new(slots);
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(&source) = sources.get(i) else { break };
+
+                    dijkstra_slots(slots, source, &mut scratch);
+                    let row = target_slots.iter()
+                        .map(|&slot| slot.map_or(usize::MAX, |slot| scratch.slot_distance(slot)))
+                        .collect();
+                    if sender.send((i, row)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        // Workers hold the remaining senders; the loop ends once all are done
+        drop(sender);
+
+        for (i, row) in receiver {
+            on_row(i, row);
+        }
+    });
+}
+
+/// Distances from every source to every target as one compact matrix.
+/// See [`batch_for_each`] for the meaning of `targets` and `threads`.
+pub fn batch_distances(graph: &Graph, sources: &[usize], targets: Option<&[usize]>, threads: usize) -> DistanceMatrix {
+    let targets: Vec<usize> = targets.unwrap_or(graph.nodes()).to_vec();
+    let width = targets.len();
+    let mut distances = vec![usize::MAX; sources.len() * width];
+
+    batch_for_each(graph, sources, Some(&targets), threads, |i, row| {
+        distances[i * width..(i + 1) * width].copy_from_slice(&row);
+    });
+
+    DistanceMatrix { sources: sources.to_vec(), targets, distances }
+}
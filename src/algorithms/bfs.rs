@@ -1,34 +1,32 @@
 use std::collections::HashMap;
+use crate::algorithms::multi_source::MultiSourceResult;
 
 
 pub fn bfs(graph: &crate::graph::Graph, start: usize) -> HashMap<usize, usize> {
- 
-    let mut distances = HashMap::new();
-    
-    // Initialize distances
-    for &node in graph.nodes() {
-        distances.insert(node, usize::MAX);
-    }
-    distances.insert(start, 0);
-    
-    
-    let mut queue = vec![start];
-    let mut visited = HashMap::new();
-    
+    multi_source_bfs(graph, &[(start, 0)]).distances
+}
+
+/// Label-correcting search from several `(source, offset)` pairs.
+///
+/// A node is re-expanded every time its distance improves, so the result is
+/// exact for any non-negative weights.
+pub fn multi_source_bfs(graph: &crate::graph::Graph, sources: &[(usize, usize)]) -> MultiSourceResult {
+    let mut result = MultiSourceResult::init(graph, sources);
+
+    let mut queue: Vec<usize> = result.nearest_source.keys().copied().collect();
+
     while let Some(current) = queue.pop() {
-        if visited.contains_key(&current) {
-            continue;
-        }
-        visited.insert(current, true);
-        
+        let source = result.nearest_source[&current];
+
         for &(neighbor, weight) in graph.neighbors(current) {
-            let new_distance = distances[&current].saturating_add(weight);
-            if new_distance < distances[&neighbor] {
-                distances.insert(neighbor, new_distance);
+            let new_distance = result.distances[&current].saturating_add(weight);
+            if new_distance < result.distance(neighbor) {
+                result.distances.insert(neighbor, new_distance);
+                result.nearest_source.insert(neighbor, source);
                 queue.push(neighbor);
             }
         }
     }
-    
-    distances
+
+    result
 }
@@ -0,0 +1,157 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::cmp::Ordering;
+use crate::graph::Graph;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct State {
+    cost: usize,
+    position: usize,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+            .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// One direction of the search: tentative distances, predecessors and the frontier
+struct Search {
+    distances: HashMap<usize, usize>,
+    predecessors: HashMap<usize, usize>,
+    heap: BinaryHeap<State>,
+}
+
+impl Search {
+    fn new(origin: usize) -> Self {
+        let mut distances = HashMap::new();
+        distances.insert(origin, 0);
+
+        let mut heap = BinaryHeap::new();
+        heap.push(State { cost: 0, position: origin });
+
+        Search {
+            distances,
+            predecessors: HashMap::new(),
+            heap,
+        }
+    }
+
+    fn distance(&self, node: usize) -> usize {
+        *self.distances.get(&node).unwrap_or(&usize::MAX)
+    }
+
+    // Smallest key still in the frontier, skipping outdated entries
+    fn min_key(&mut self) -> usize {
+        while let Some(&State { cost, position }) = self.heap.peek() {
+            if cost > self.distance(position) {
+                self.heap.pop();
+            } else {
+                return cost;
+            }
+        }
+        usize::MAX
+    }
+
+    /// Settle the next node and relax its edges in `graph`. Every relaxed node
+    /// that the `other` search has already reached is a candidate meeting point.
+    fn step(&mut self, graph: &Graph, other: &Search, best: &mut usize, meeting: &mut Option<usize>) {
+        let State { cost, position } = match self.heap.pop() {
+            Some(state) => state,
+            None => return,
+        };
+        if cost > self.distance(position) {
+            return;
+        }
+
+        for &(neighbor, weight) in graph.neighbors(position) {
+            let next_cost = cost.saturating_add(weight);
+
+            if next_cost < self.distance(neighbor) {
+                self.distances.insert(neighbor, next_cost);
+                self.predecessors.insert(neighbor, position);
+                self.heap.push(State { cost: next_cost, position: neighbor });
+            }
+
+            let through = self.distance(neighbor).saturating_add(other.distance(neighbor));
+            if through < *best {
+                *best = through;
+                *meeting = Some(neighbor);
+            }
+        }
+    }
+}
+
+/// Bidirectional Dijkstra for a single `source -> target` query.
+///
+/// Builds the reverse graph on every call; use [`bidirectional_dijkstra_with_reverse`]
+/// when answering many queries on the same graph.
+/// Returns the distance and the path (including both endpoints), or `None` if
+/// `target` is unreachable.
+pub fn bidirectional_dijkstra(graph: &Graph, source: usize, target: usize) -> Option<(usize, Vec<usize>)> {
+    let reverse = graph.reversed();
+    bidirectional_dijkstra_with_reverse(graph, &reverse, source, target)
+}
+
+/// Bidirectional Dijkstra using a precomputed reverse graph (see [`Graph::reversed`])
+pub fn bidirectional_dijkstra_with_reverse(
+    graph: &Graph,
+    reverse: &Graph,
+    source: usize,
+    target: usize,
+) -> Option<(usize, Vec<usize>)> {
+    if source == target {
+        return Some((0, vec![source]));
+    }
+
+    let mut forward = Search::new(source);
+    let mut backward = Search::new(target);
+    let mut best = usize::MAX;
+    let mut meeting = None;
+
+    loop {
+        let top_forward = forward.min_key();
+        let top_backward = backward.min_key();
+
+        // Stop once no undiscovered path can be shorter than the best one seen:
+        // any such path would need a node with d_f + d_b < best.
+        if top_forward == usize::MAX || top_backward == usize::MAX
+            || top_forward.saturating_add(top_backward) >= best
+        {
+            break;
+        }
+
+        // Expand the side with the smaller frontier key
+        if top_forward <= top_backward {
+            forward.step(graph, &backward, &mut best, &mut meeting);
+        } else {
+            backward.step(reverse, &forward, &mut best, &mut meeting);
+        }
+    }
+
+    let meeting = meeting?;
+
+    // source -> meeting from the forward predecessors
+    let mut path = vec![meeting];
+    let mut current = meeting;
+    while let Some(&previous) = forward.predecessors.get(&current) {
+        path.push(previous);
+        current = previous;
+    }
+    path.reverse();
+
+    // meeting -> target from the backward predecessors
+    current = meeting;
+    while let Some(&next) = backward.predecessors.get(&current) {
+        path.push(next);
+        current = next;
+    }
+
+    Some((best, path))
+}
@@ -1,208 +1,379 @@
 // https://arxiv.org/pdf/2504.17033
 
-use std::collections::{ BinaryHeap, HashMap, HashSet};
-use lazy_static::lazy_static;
-use std::sync::Mutex;
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::cmp::Ordering;
-lazy_static! {
-    static ref DISTANCES: Mutex<HashMap<usize, usize>> = Mutex::new(HashMap::new());
-}
+use crate::algorithms::multi_source::MultiSourceResult;
+use crate::algorithms::workspace::{DistanceMap, QueryWorkspace};
+use crate::graph::Graph;
 
-// Calculate k := ⌊log₁/₃(n)⌋ 
+// Calculate k := ⌊log^(1/3)(n)⌋
 fn calculate_k(n: usize) -> usize {
-    if n <= 1 { return 1; }
-    ((n as f64).ln() / (1.0/3.0_f64).ln()).floor() as usize
+    if n <= 2 { return 1; }
+    ((n as f64).log2().powf(1.0 / 3.0).floor() as usize).max(1)
 }
 
-// Calculate t := ⌊log₂/₃(n)⌋
+// Calculate t := ⌊log^(2/3)(n)⌋
 fn calculate_t(n: usize) -> usize {
-    if n <= 1 { return 1; }
-    ((n as f64).ln() / (2.0/3.0_f64).ln()).floor() as usize
+    if n <= 2 { return 1; }
+    ((n as f64).log2().powf(2.0 / 3.0).floor() as usize).max(1)
 }
 
-fn findPivot(
-    bound: usize, 
-    graph: &crate::graph::Graph,
-    s: &HashSet<usize>, // Set of source vertices
-) -> (HashSet<usize>, HashSet<usize>) { // Returns (P, W)
-    let n = graph.nodes().len();
-    let k = calculate_k(n); // k := ⌊log₁/₃(n)⌋
-    let mut distances = DISTANCES.lock().unwrap();
-    
-    let mut w = s.clone(); // W ← S
-    let mut w_prev = s.clone(); // W0 ← S
-    
-    // Relax for k steps
-    for i in 1..=k {
-        let mut w_i = HashSet::new(); // Wi ← ∅
-        
-        // For all edges (u,v) with u ∈ Wi-1
-        for &u in &w_prev {
-            for &(v, weight) in graph.neighbors(u) {
-                let d_u = *distances.get(&u).unwrap_or(&usize::MAX);
-                let d_v = distances.get(&v).unwrap_or(&usize::MAX);
-                
-                // if d[u] + wuv ≤ d[v] then
-                if d_u.saturating_add(weight) <= *d_v {
-                    // d[v] ← d[u] + wuv
-                    distances.insert(v, d_u + weight);
-                    
-                    // if d[u] + wuv < B then
-                    if d_u.saturating_add(weight) < bound {
-                        // Wi ← Wi ∪ {v}
-                        w_i.insert(v);
+// Top recursion level l := ⌈log(n) / t⌉, at which a single call settles everything below the bound
+fn calculate_level(n: usize, t: usize) -> usize {
+    if n <= 2 { return 1; }
+    ((n as f64).log2() / t as f64).ceil() as usize
+}
+
+// 2^exponent, saturating instead of overflowing on huge levels
+fn pow2(exponent: usize) -> usize {
+    u32::try_from(exponent).ok().and_then(|e| 1usize.checked_shl(e)).unwrap_or(usize::MAX)
+}
+
+/// Stand-in for the block-based structure D of Lemma 3.3: supports Insert,
+/// BatchPrepend and Pull, keeping the smallest value seen for each key.
+struct PartialQueue {
+    values: HashMap<usize, usize>,
+    ordered: BTreeSet<(usize, usize)>, // (value, key)
+}
+
+impl PartialQueue {
+    fn new() -> Self {
+        PartialQueue { values: HashMap::new(), ordered: BTreeSet::new() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ordered.is_empty()
+    }
+
+    fn insert(&mut self, key: usize, value: usize) {
+        match self.values.get(&key) {
+            Some(&old) if old <= value => return,
+            Some(&old) => { self.ordered.remove(&(old, key)); }
+            None => {}
+        }
+        self.values.insert(key, value);
+        self.ordered.insert((value, key));
+    }
+
+    // Every value in `items` is smaller than all values currently stored
+    fn batch_prepend(&mut self, items: Vec<(usize, usize)>) {
+        for (key, value) in items {
+            self.insert(key, value);
+        }
+    }
+
+    /// Remove (at least) the `m` smallest keys and return them together with a
+    /// separator: every returned value is below it and every remaining value is
+    /// at or above it. Ties at the cut are pulled together, so the separator is
+    /// always strict.
+    fn pull(&mut self, m: usize, bound: usize) -> (usize, HashSet<usize>) {
+        let mut pulled = HashSet::new();
+        let mut last = None;
+
+        while let Some(&(value, key)) = self.ordered.iter().next() {
+            if pulled.len() >= m && last != Some(value) {
+                break;
+            }
+            self.ordered.remove(&(value, key));
+            self.values.remove(&key);
+            pulled.insert(key);
+            last = Some(value);
+        }
+
+        let separator = self.ordered.iter().next().map_or(bound, |&(value, _)| value);
+        (separator, pulled)
+    }
+}
+
+/// Shared state of one BMSSP run: the tentative distances d̂ and, when
+/// tracked, the source whose path set each reached node's current d̂
+struct Bmssp<'a, D: DistanceMap> {
+    graph: &'a Graph,
+    k: usize,
+    t: usize,
+    distances: D,
+    origins: Option<HashMap<usize, usize>>,
+}
+
+impl<D: DistanceMap> Bmssp<'_, D> {
+    fn new(graph: &Graph, distances: D, origins: Option<HashMap<usize, usize>>) -> Bmssp<'_, D> {
+        let n = graph.nodes().len();
+        Bmssp { graph, k: calculate_k(n), t: calculate_t(n), distances, origins }
+    }
+
+    fn distance(&self, node: usize) -> usize {
+        self.distances.get_distance(node)
+    }
+
+    // d̂[v] ← d̂[u] + w_uv if that is no worse; returns the candidate when applied
+    fn relax(&mut self, u: usize, v: usize, weight: usize) -> Option<usize> {
+        let candidate = self.distance(u).saturating_add(weight);
+        if candidate == usize::MAX || candidate > self.distance(v) {
+            return None;
+        }
+        self.distances.set_distance(v, candidate);
+        if let Some(origins) = &mut self.origins {
+            if let Some(&origin) = origins.get(&u) {
+                origins.insert(v, origin);
+            }
+        }
+        Some(candidate)
+    }
+
+    fn find_pivots(
+        &mut self,
+        bound: usize,
+        s: &HashSet<usize>, // Set of source vertices
+    ) -> (HashSet<usize>, HashSet<usize>) { // Returns (P, W)
+        let k = self.k;
+        let graph = self.graph;
+
+        let mut w = s.clone(); // W ← S
+        let mut w_prev = s.clone(); // W0 ← S
+
+        // Relax for k steps
+        for _ in 1..=k {
+            let mut w_i = HashSet::new(); // Wi ← ∅
+
+            // For all edges (u,v) with u ∈ Wi-1
+            for &u in &w_prev {
+                for &(v, weight) in graph.neighbors(u) {
+                    // if d[u] + wuv ≤ d[v] then d[v] ← d[u] + wuv
+                    if let Some(d_v) = self.relax(u, v, weight) {
+                        // if d[u] + wuv < B then Wi ← Wi ∪ {v}
+                        if d_v < bound {
+                            w_i.insert(v);
+                        }
                     }
                 }
             }
+
+            // W ← W ∪ Wi
+            w.extend(&w_i);
+            w_prev = w_i;
+
+            // if |W| > k|S| then
+            if w.len() > k * s.len() {
+                // P ← S
+                return (s.clone(), w);
+            }
         }
-        
-        // W ← W ∪ Wi
-        w.extend(&w_i);
-        w_prev = w_i;
-        
-        // if |W| > k|S| then
-        if w.len() > k * s.len() {
-            // P ← S
-            return (s.clone(), w);
+
+        // F ← {(u,v) ∈ E : u,v ∈ W, d[v] = d[u] + wuv}
+        // F is a directed forest under Assumption 2.1 (and without zero-weight cycles)
+        let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for &u in &w {
+            for &(v, weight) in graph.neighbors(u) {
+                if w.contains(&v) && self.distance(v) == self.distance(u).saturating_add(weight) {
+                    children.entry(u).or_default().push(v);
+                }
+            }
         }
+
+        // P ← {u ∈ S : u is a root of a tree with ≥ k vertices in F}
+        let p = s.iter()
+            .copied()
+            .filter(|&u| count_tree_vertices(&children, u) >= k)
+            .collect();
+
+        (p, w)
     }
-    
-    // F ← {(u,v) ∈ E : u,v ∈ W, d[v] = d[u] + wuv}
-    // F is a directed forest under Assumption 2.1
-    let mut forest_edges: Vec<(usize, usize)> = Vec::new();
-    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
-    
-    for &u in &w {
-        for &(v, weight) in graph.neighbors(u) {
-            if w.contains(&v) {
-                let d_u = distances.get(&u).unwrap_or(&usize::MAX);
-                let d_v = distances.get(&v).unwrap_or(&usize::MAX);
-                
-                if *d_v == d_u.saturating_add(weight) {
-                    forest_edges.push((u, v));
-                    children.entry(u).or_insert_with(Vec::new).push(v);
+
+    /// Bounded Dijkstra from `s` settling about k + 1 vertices.
+    ///
+    /// The paper starts from a single vertex; a source set only appears here
+    /// when pulls had to keep tied values together. The returned bound is the
+    /// next unsettled distance rather than the largest settled one, so ties
+    /// never leave the settled set empty.
+    fn base_case(&mut self, boundary: usize, s: &HashSet<usize>) -> (usize, HashSet<usize>) {
+        let k = self.k;
+        let graph = self.graph;
+        let mut u_0 = HashSet::new();
+        let mut heap = BinaryHeap::new();
+        let mut last_settled = 0;
+
+        for &x in s {
+            heap.push(State { cost: self.distance(x), node: x });
+        }
+
+        while let Some(State { cost: d_u, node: u }) = heap.pop() {
+            // Skip if this is an outdated entry
+            if d_u > self.distance(u) || u_0.contains(&u) {
+                continue;
+            }
+
+            // Once more than k vertices are settled, stop at the next strictly larger distance
+            if u_0.len() > k && d_u > last_settled {
+                return (d_u, u_0);
+            }
+            u_0.insert(u);
+            last_settled = d_u;
+
+            for &(v, weight) in graph.neighbors(u) {
+                let new_distance = d_u.saturating_add(weight);
+
+                if new_distance < boundary && !u_0.contains(&v) && self.relax(u, v, weight).is_some() {
+                    // BinaryHeap has no decrease-key: push again and skip outdated entries
+                    heap.push(State { cost: new_distance, node: v });
                 }
             }
         }
+
+        (boundary, u_0)
     }
-    
-    // P ← {u ∈ S : u is a root of a tree with ≥ k vertices in F}
-    let mut p = HashSet::new();
-    
-    for &u in s {
-        if count_tree_vertices(&children, u) >= k {
-            p.insert(u);
+
+    /// BMSSP(l, B, S): returns B' ≤ B and every vertex with d < B' whose
+    /// shortest path visits S, all of them complete
+    fn run(&mut self, level: usize, bound: usize, s: &HashSet<usize>) -> (usize, HashSet<usize>) {
+        if level == 0 {
+            return self.base_case(bound, s);
         }
-    }
-    
-    (p, w)
-}
 
-fn count_tree_vertices(children: &HashMap<usize, Vec<usize>>, root: usize) -> usize{
+        let (p, w) = self.find_pivots(bound, s);
+
+        // D.Initialize(M := 2^((l-1)t), B), then insert every pivot
+        let m = pow2((level - 1) * self.t);
+        let mut queue = PartialQueue::new();
+        for &x in &p {
+            queue.insert(x, self.distance(x));
+        }
+
+        // B'_0 ← min over P of d̂[x]
+        let mut last_bound = p.iter().map(|&x| self.distance(x)).min().unwrap_or(bound);
+        let mut u = HashSet::new();
+        let limit = self.k.saturating_mul(pow2(level * self.t));
 
-        let mut count = 1;
+        while u.len() < limit && !queue.is_empty() {
+            let (b_i, s_i) = queue.pull(m, bound);
+            let (b_prime_i, u_i) = self.run(level - 1, b_i, &s_i);
 
-        if let Some(child) = children.get(&root)  {
-            for &node in child{
-                count += count_tree_vertices(children, node);
+            let mut prepend = Vec::new();
+            for &x in &u_i {
+                for &(v, weight) in self.graph.neighbors(x) {
+                    if let Some(d_v) = self.relax(x, v, weight) {
+                        if (b_i..bound).contains(&d_v) {
+                            queue.insert(v, d_v);
+                        } else if (b_prime_i..b_i).contains(&d_v) {
+                            prepend.push((v, d_v));
+                        }
+                    }
+                }
             }
+            // Sources the sub-call did not finish go back to the front
+            for &x in &s_i {
+                let d_x = self.distance(x);
+                if (b_prime_i..b_i).contains(&d_x) {
+                    prepend.push((x, d_x));
+                }
+            }
+            queue.batch_prepend(prepend);
+
+            u.extend(u_i);
+            last_bound = b_prime_i;
         }
-        count
+
+        // B' ← min{B'_i, B}; U ← U ∪ {x ∈ W : d̂[x] < B'}
+        let new_bound = last_bound.min(bound);
+        u.extend(w.into_iter().filter(|&x| self.distance(x) < new_bound));
+
+        (new_bound, u)
+    }
 }
-fn baseCase(boundary: usize, singleton: usize, graph: &crate::graph::Graph) -> (usize, HashSet<usize>) {
-    let mut distances = DISTANCES.lock().unwrap();
-    let mut Uo = HashSet::new();
-    Uo.insert(singleton);
-    let n = graph.nodes().len();
-    let k = calculate_k(n); // k := ⌊log₁/₃(n)⌋
-    let mut H = BinaryHeap::new();
-    let mut in_heap = HashSet::new(); // Track which nodes are in heap
-    
-    H.push(State{cost: distances[&singleton], node: singleton });
-    in_heap.insert(singleton);
-    
-    while !H.is_empty() && Uo.len() < k + 1 {
-        let State { cost: d_u, node: u } = H.pop().unwrap();
-        
-        // Skip if this is an outdated entry
-        if d_u > distances[&u] {
-            continue;
-        }
-        
-        in_heap.remove(&u);
-        Uo.insert(u);
-        
-        for &(v, weight) in graph.neighbors(u) {
-            let d_v = distances.get(&v).unwrap_or(&usize::MAX);
-            let new_distance = d_u.saturating_add(weight);
-            
-            if new_distance <= *d_v && new_distance < boundary {
-                distances.insert(v, new_distance);
-                
-                // if v is not in H then H.Insert(⟨v, d[v]⟩)
-                if !in_heap.contains(&v) {
-                    H.push(State { cost: new_distance, node: v });
-                    in_heap.insert(v);
-                } else {
-                    // else H.DecreaseKey(⟨v, d[v]⟩)
-                    // Since BinaryHeap doesn't support decrease-key directly,
-                    // we insert the new value and ignore outdated entries when popping
-                    H.push(State { cost: new_distance, node: v });
+
+// Vertices reachable from `root` in F, each counted once. Under Assumption 2.1
+// F is a forest and this is the size of root's tree; zero-weight cycles make
+// F cyclic, and ties can give a vertex several parents.
+fn count_tree_vertices(children: &HashMap<usize, Vec<usize>>, root: usize) -> usize {
+    let mut visited = HashSet::from([root]);
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        if let Some(child) = children.get(&node) {
+            for &next in child {
+                if visited.insert(next) {
+                    stack.push(next);
                 }
             }
         }
     }
 
-//      if |U0| ≤ k then
-//          return B′ ← B,U ← U0
-//          else
-//          return B′ ← maxv∈U0 d[v],U ← {v ∈ U0 : d[v] < B′}
-    if Uo.len() <= k {
-
-        (boundary, Uo)
-    } else {
-        let max_distance = Uo.iter().map(|&v| distances[&v]).max().unwrap_or(usize::MAX);
-        let filtered: HashSet<_> = Uo.into_iter().filter(|&v| distances[&v] < max_distance).collect();
-        (max_distance, filtered)
-    }
+    visited.len()
+}
 
+fn new_run<'a>(
+    graph: &'a Graph,
+    sources_with_distances: &[(usize, usize)],
+) -> (Bmssp<'a, HashMap<usize, usize>>, HashSet<usize>) {
+    let initial = MultiSourceResult::init(graph, sources_with_distances);
+    let sources = initial.nearest_source.keys().copied().collect();
 
+    (Bmssp::new(graph, initial.distances, Some(initial.nearest_source)), sources)
 }
-pub fn bmssp(graph: &crate::graph::Graph, start: usize) -> HashMap<usize, usize> {
+
+/// The paper's bounded multi-source shortest path procedure, run at the top
+/// recursion level.
+///
+/// `sources_with_distances` are `(node, distance)` pairs whose distances are
+/// taken as final. Returns `(B', U, d̂)`: every node whose shortest path from
+/// the sources is shorter than `B'` is in `U` with its exact distance in `d̂`.
+/// At the top level `B'` always equals `bound`; nodes outside `U` keep an
+/// upper bound (or `usize::MAX`) in `d̂`. Sources whose distance is not below
+/// `bound` are ignored.
+pub fn bounded_msssp(
+    graph: &Graph,
+    sources_with_distances: &[(usize, usize)],
+    bound: usize,
+) -> (usize, HashSet<usize>, HashMap<usize, usize>) {
     let n = graph.nodes().len();
-    let k = calculate_k(n); // k := ⌊log₁/₃(n)⌋  
-    let t = calculate_t(n); // t := ⌊log₂/₃(n)⌋
-    
-    let mut distances = DISTANCES.lock().unwrap();
-    distances.clear(); // Reset global distances for this run
-    
-    // Initialize distances
-    for &node in graph.nodes() {
-        distances.insert(node, usize::MAX);
-    }
-    distances.insert(start, 0);
-    
-    
-    let mut queue = vec![start];
-    let mut visited = HashMap::new();
-    
-    while let Some(current) = queue.pop() {
-        if visited.contains_key(&current) {
-            continue;
-        }
-        visited.insert(current, true);
-        
-        for &(neighbor, weight) in graph.neighbors(current) {
-            let new_distance = distances[&current].saturating_add(weight);
-            if new_distance < distances[&neighbor] {
-                distances.insert(neighbor, new_distance);
-                queue.push(neighbor);
-            }
-        }
-    }
-    
-    distances
-}   
+    bounded_msssp_at_level(graph, sources_with_distances, bound, calculate_level(n, calculate_t(n)))
+}
+
+/// [`bounded_msssp`] at an explicit recursion level `l`.
+///
+/// Below the top level a call may stop early once about k·2^(l·t) nodes are
+/// settled, returning a smaller `B'`; `U` is still complete below it.
+pub fn bounded_msssp_at_level(
+    graph: &Graph,
+    sources_with_distances: &[(usize, usize)],
+    bound: usize,
+    level: usize,
+) -> (usize, HashSet<usize>, HashMap<usize, usize>) {
+    let below_bound: Vec<(usize, usize)> = sources_with_distances.iter()
+        .copied()
+        .filter(|&(_, distance)| distance < bound)
+        .collect();
+    let (mut run, sources) = new_run(graph, &below_bound);
+    let (new_bound, settled) = run.run(level, bound, &sources);
+    (new_bound, settled, run.distances)
+}
+
+pub fn bmssp(graph: &Graph, start: usize) -> HashMap<usize, usize> {
+    multi_source_bmssp(graph, &[(start, 0)]).distances
+}
+
+/// BMSSP from several `(source, offset)` pairs, also reporting each node's nearest source
+pub fn multi_source_bmssp(graph: &Graph, sources: &[(usize, usize)]) -> MultiSourceResult {
+    let n = graph.nodes().len();
+    let level = calculate_level(n, calculate_t(n));
+
+    let (mut run, sources) = new_run(graph, sources);
+    run.run(level, usize::MAX, &sources);
+
+    MultiSourceResult { distances: run.distances, nearest_source: run.origins.unwrap_or_default() }
+}
+
+/// [`bmssp`] reusing `workspace` for the distance array; read the result from the workspace
+pub fn bmssp_with_workspace(graph: &Graph, start: usize, workspace: &mut QueryWorkspace) {
+    let n = graph.nodes().len();
+    let level = calculate_level(n, calculate_t(n));
+
+    workspace.reset();
+    workspace.set_distance(start, 0);
+
+    let mut run = Bmssp::new(graph, workspace, None);
+    run.run(level, usize::MAX, &HashSet::from([start]));
+}
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 struct State {
@@ -221,4 +392,4 @@ impl PartialOrd for State {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
-}
\ No newline at end of file
+}
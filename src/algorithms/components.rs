@@ -0,0 +1,155 @@
+use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use crate::graph::Graph;
+
+/// Strongly connected components (Tarjan), each a list of node ids.
+///
+/// Components come out in reverse topological order of the condensation: no
+/// edge leads from a component to one listed after it. The search is iterative,
+/// so long paths do not overflow the stack.
+pub fn strongly_connected_components(graph: &Graph) -> Vec<Vec<usize>> {
+    let mut index: HashMap<usize, usize> = HashMap::new();
+    let mut low: HashMap<usize, usize> = HashMap::new();
+    let mut on_stack = HashSet::new();
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut counter = 0;
+
+    for &root in graph.nodes() {
+        if index.contains_key(&root) {
+            continue;
+        }
+
+        // Each frame is a node and the position of the next edge to look at
+        let mut calls = vec![(root, 0)];
+        index.insert(root, counter);
+        low.insert(root, counter);
+        counter += 1;
+        stack.push(root);
+        on_stack.insert(root);
+
+        while let Some(&mut (node, ref mut edge)) = calls.last_mut() {
+            if let Some(&(neighbor, _)) = graph.neighbors(node).get(*edge) {
+                *edge += 1;
+
+                match index.get(&neighbor) {
+                    None => {
+                        index.insert(neighbor, counter);
+                        low.insert(neighbor, counter);
+                        counter += 1;
+                        stack.push(neighbor);
+                        on_stack.insert(neighbor);
+                        calls.push((neighbor, 0));
+                    }
+                    Some(&neighbor_index) if on_stack.contains(&neighbor) => {
+                        let lowest = low[&node].min(neighbor_index);
+                        low.insert(node, lowest);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(parent, _)) = calls.last() {
+                let lowest = low[&parent].min(low[&node]);
+                low.insert(parent, lowest);
+            }
+
+            if low[&node] == index[&node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack.remove(&member);
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Weakly connected components: connected components when edge directions
+/// are ignored
+pub fn weakly_connected_components(graph: &Graph) -> Vec<Vec<usize>> {
+    let reverse = graph.reversed();
+    let mut seen = HashSet::new();
+    let mut components = Vec::new();
+
+    for &root in graph.nodes() {
+        if seen.contains(&root) {
+            continue;
+        }
+
+        let mut component = vec![root];
+        let mut stack = vec![root];
+        seen.insert(root);
+
+        while let Some(node) = stack.pop() {
+            let edges = graph.neighbors(node).iter().chain(reverse.neighbors(node));
+            for &(neighbor, _) in edges {
+                if seen.insert(neighbor) {
+                    component.push(neighbor);
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        components.push(component);
+    }
+
+    components
+}
+
+/// Which nodes a single-source search from `source` can reach
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReachabilityReport {
+    pub source: usize,
+    pub reached: usize,
+    pub total: usize,
+    pub unreached: Vec<usize>,
+}
+
+impl ReachabilityReport {
+    /// Share of the graph's nodes that are reachable, in `0.0..=1.0`
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 { 0.0 } else { self.reached as f64 / self.total as f64 }
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.unreached.is_empty()
+    }
+}
+
+/// Reachability from `source`, ignoring weights. The `reached` count is the
+/// number of finite entries every exact SSSP algorithm must return.
+pub fn reachability(graph: &Graph, source: usize) -> ReachabilityReport {
+    let mut seen = HashSet::new();
+    let mut stack = Vec::new();
+
+    if graph.nodes().contains(&source) {
+        seen.insert(source);
+        stack.push(source);
+    }
+
+    while let Some(node) = stack.pop() {
+        for &(neighbor, _) in graph.neighbors(node) {
+            if seen.insert(neighbor) {
+                stack.push(neighbor);
+            }
+        }
+    }
+
+    let unreached: Vec<usize> = graph.nodes().iter().copied().filter(|node| !seen.contains(node)).collect();
+
+    ReachabilityReport {
+        source,
+        reached: graph.node_count() - unreached.len(),
+        total: graph.node_count(),
+        unreached,
+    }
+}
@@ -0,0 +1,291 @@
+// Contraction hierarchies (Geisberger et al., 2008)
+
+use std::collections::{BinaryHeap, HashMap};
+use std::cmp::{Ordering, Reverse};
+use crate::graph::Graph;
+
+// Witness searches give up after settling this many nodes. Giving up early only
+// adds superfluous shortcuts, never wrong ones.
+const WITNESS_SETTLE_LIMIT: usize = 500;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct State {
+    cost: usize,
+    position: usize,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+            .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Preprocessed graph answering point-to-point queries with two upward searches.
+///
+/// Nodes are contracted in order of increasing importance; contracting a node
+/// inserts shortcuts between its neighbours wherever it lies on the only
+/// shortest path between them. Queries then only ever move to more important nodes.
+#[derive(Debug, Clone)]
+pub struct ContractionHierarchy {
+    node_ids: Vec<usize>,
+    index: HashMap<usize, usize>,
+    rank: Vec<usize>,
+    /// `upward[u]` holds edges `u -> v` with `rank[v] > rank[u]`
+    upward: Vec<Vec<(usize, usize)>>,
+    /// `downward[v]` holds edges `u -> v` with `rank[u] > rank[v]`, stored as `(u, weight)`
+    downward: Vec<Vec<(usize, usize)>>,
+    /// Contracted node bypassed by each shortcut `(u, v)`
+    middle: HashMap<(usize, usize), usize>,
+}
+
+/// Mutable overlay graph used while contracting
+struct Overlay {
+    outgoing: Vec<HashMap<usize, usize>>,
+    incoming: Vec<HashMap<usize, usize>>,
+    contracted: Vec<bool>,
+}
+
+impl Overlay {
+    /// Shortcuts `(u, w, weight)` needed if `node` were contracted now
+    fn shortcuts(&self, node: usize) -> Vec<(usize, usize, usize)> {
+        let mut shortcuts = Vec::new();
+        let max_out = self.outgoing[node].values().copied().max().unwrap_or(0);
+
+        for (&from, &in_weight) in &self.incoming[node] {
+            let bound = in_weight.saturating_add(max_out);
+            let witness = self.witness_search(from, node, bound);
+
+            for (&to, &out_weight) in &self.outgoing[node] {
+                if to == from {
+                    continue;
+                }
+                let via = in_weight.saturating_add(out_weight);
+                if *witness.get(&to).unwrap_or(&usize::MAX) > via {
+                    shortcuts.push((from, to, via));
+                }
+            }
+        }
+
+        shortcuts
+    }
+
+    // Bounded Dijkstra from `from` that never passes through `avoid`
+    fn witness_search(&self, from: usize, avoid: usize, bound: usize) -> HashMap<usize, usize> {
+        let mut distances = HashMap::new();
+        let mut heap = BinaryHeap::new();
+        let mut settled = 0;
+
+        distances.insert(from, 0);
+        heap.push(State { cost: 0, position: from });
+
+        while let Some(State { cost, position }) = heap.pop() {
+            if cost > distances[&position] {
+                continue;
+            }
+            if cost > bound || settled >= WITNESS_SETTLE_LIMIT {
+                break;
+            }
+            settled += 1;
+
+            for (&neighbor, &weight) in &self.outgoing[position] {
+                if neighbor == avoid {
+                    continue;
+                }
+                let next_cost = cost.saturating_add(weight);
+                if next_cost < *distances.get(&neighbor).unwrap_or(&usize::MAX) {
+                    distances.insert(neighbor, next_cost);
+                    heap.push(State { cost: next_cost, position: neighbor });
+                }
+            }
+        }
+
+        distances
+    }
+
+    fn priority(&self, node: usize, contracted_neighbors: &[usize]) -> i64 {
+        let shortcuts = self.shortcuts(node).len() as i64;
+        let removed = (self.incoming[node].len() + self.outgoing[node].len()) as i64;
+        shortcuts - removed + contracted_neighbors[node] as i64
+    }
+}
+
+impl ContractionHierarchy {
+    /// Contract every node of `graph` and build the upward search graphs
+    pub fn build(graph: &Graph) -> Self {
+        let node_ids: Vec<usize> = graph.nodes().clone();
+        let index: HashMap<usize, usize> = node_ids.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        let n = node_ids.len();
+
+        let mut overlay = Overlay {
+            outgoing: vec![HashMap::new(); n],
+            incoming: vec![HashMap::new(); n],
+            contracted: vec![false; n],
+        };
+        for (u, &node) in node_ids.iter().enumerate() {
+            for &(neighbor, weight) in graph.neighbors(node) {
+                let v = index[&neighbor];
+                if u == v {
+                    continue;
+                }
+                // Keep only the cheapest of parallel edges
+                let entry = overlay.outgoing[u].entry(v).or_insert(usize::MAX);
+                *entry = (*entry).min(weight);
+                overlay.incoming[v].insert(u, *entry);
+            }
+        }
+
+        // Lazy priority queue keyed on edge difference + contracted neighbours
+        let mut contracted_neighbors = vec![0; n];
+        let mut queue: BinaryHeap<Reverse<(i64, usize)>> = (0..n)
+            .map(|v| Reverse((overlay.priority(v, &contracted_neighbors), v)))
+            .collect();
+
+        let mut rank = vec![0; n];
+        let mut upward = vec![Vec::new(); n];
+        let mut downward = vec![Vec::new(); n];
+        let mut middle = HashMap::new();
+        let mut next_rank = 0;
+
+        while let Some(Reverse((priority, node))) = queue.pop() {
+            if overlay.contracted[node] {
+                continue;
+            }
+            let current = overlay.priority(node, &contracted_neighbors);
+            if current > priority {
+                if let Some(&Reverse((next, _))) = queue.peek() {
+                    if current > next {
+                        queue.push(Reverse((current, node)));
+                        continue;
+                    }
+                }
+            }
+
+            rank[node] = next_rank;
+            next_rank += 1;
+
+            // All remaining neighbours will be ranked higher than `node`
+            upward[node] = overlay.outgoing[node].iter().map(|(&v, &w)| (v, w)).collect();
+            downward[node] = overlay.incoming[node].iter().map(|(&u, &w)| (u, w)).collect();
+
+            for (from, to, weight) in overlay.shortcuts(node) {
+                let existing = overlay.outgoing[from].get(&to).copied().unwrap_or(usize::MAX);
+                if weight < existing {
+                    overlay.outgoing[from].insert(to, weight);
+                    overlay.incoming[to].insert(from, weight);
+                    middle.insert((from, to), node);
+                }
+            }
+
+            overlay.contracted[node] = true;
+            let outgoing = std::mem::take(&mut overlay.outgoing[node]);
+            let incoming = std::mem::take(&mut overlay.incoming[node]);
+            for &v in outgoing.keys() {
+                overlay.incoming[v].remove(&node);
+                contracted_neighbors[v] += 1;
+            }
+            for &u in incoming.keys() {
+                overlay.outgoing[u].remove(&node);
+                contracted_neighbors[u] += 1;
+            }
+        }
+
+        ContractionHierarchy { node_ids, index, rank, upward, downward, middle }
+    }
+
+    /// Number of shortcut edges added during preprocessing
+    pub fn shortcut_count(&self) -> usize {
+        self.middle.len()
+    }
+
+    /// Shortest distance from `source` to `target`, `None` if unreachable
+    pub fn distance(&self, source: usize, target: usize) -> Option<usize> {
+        self.search(source, target).map(|(distance, _, _, _)| distance)
+    }
+
+    /// Shortest distance and the unpacked path in the original graph
+    pub fn path(&self, source: usize, target: usize) -> Option<(usize, Vec<usize>)> {
+        let (distance, meeting, forward, backward) = self.search(source, target)?;
+
+        // Up-down path through the hierarchy, still containing shortcuts
+        let mut packed = vec![meeting];
+        let mut current = meeting;
+        while let Some(&previous) = forward.get(&current) {
+            packed.push(previous);
+            current = previous;
+        }
+        packed.reverse();
+        current = meeting;
+        while let Some(&next) = backward.get(&current) {
+            packed.push(next);
+            current = next;
+        }
+
+        let mut path = vec![self.node_ids[packed[0]]];
+        for pair in packed.windows(2) {
+            self.unpack(pair[0], pair[1], &mut path);
+        }
+
+        Some((distance, path))
+    }
+
+    // Append the original-graph nodes of edge `from -> to` (excluding `from`)
+    fn unpack(&self, from: usize, to: usize, path: &mut Vec<usize>) {
+        match self.middle.get(&(from, to)) {
+            Some(&via) => {
+                self.unpack(from, via, path);
+                self.unpack(via, to, path);
+            }
+            None => path.push(self.node_ids[to]),
+        }
+    }
+
+    /// Upward searches from both ends. Returns the distance, the meeting node
+    /// and the predecessor maps of the forward and backward search.
+    #[allow(clippy::type_complexity)]
+    fn search(&self, source: usize, target: usize) -> Option<(usize, usize, HashMap<usize, usize>, HashMap<usize, usize>)> {
+        let s = *self.index.get(&source)?;
+        let t = *self.index.get(&target)?;
+
+        let (forward_dist, forward_pred) = self.upward_search(s, &self.upward);
+        let (backward_dist, backward_pred) = self.upward_search(t, &self.downward);
+
+        let (distance, meeting) = forward_dist.iter()
+            .filter_map(|(&node, &d)| backward_dist.get(&node).map(|&b| (d.saturating_add(b), node)))
+            .min_by_key(|&(distance, node)| (distance, self.rank[node]))?;
+
+        Some((distance, meeting, forward_pred, backward_pred))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn upward_search(&self, origin: usize, edges: &[Vec<(usize, usize)>]) -> (HashMap<usize, usize>, HashMap<usize, usize>) {
+        let mut distances = HashMap::new();
+        let mut predecessors = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        distances.insert(origin, 0);
+        heap.push(State { cost: 0, position: origin });
+
+        while let Some(State { cost, position }) = heap.pop() {
+            if cost > distances[&position] {
+                continue;
+            }
+            for &(neighbor, weight) in &edges[position] {
+                let next_cost = cost.saturating_add(weight);
+                if next_cost < *distances.get(&neighbor).unwrap_or(&usize::MAX) {
+                    distances.insert(neighbor, next_cost);
+                    predecessors.insert(neighbor, position);
+                    heap.push(State { cost: next_cost, position: neighbor });
+                }
+            }
+        }
+
+        (distances, predecessors)
+    }
+}
@@ -1,5 +1,8 @@
 use std::collections::{BinaryHeap, HashMap};
 use std::cmp::Ordering;
+use crate::algorithms::multi_source::MultiSourceResult;
+use crate::algorithms::shortest_path_tree::ShortestPathTree;
+use crate::graph::GraphMask;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
 struct State {
@@ -50,3 +53,121 @@ pub fn dijkstra(graph: &crate::graph::Graph, start: usize) -> HashMap<usize, usi
     
     distances
 }
+
+/// Dijkstra's algorithm that also records the shortest-path tree
+pub fn dijkstra_tree(graph: &crate::graph::Graph, start: usize) -> ShortestPathTree {
+    let mut distances = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    
+    for &node in graph.nodes() {
+        distances.insert(node, usize::MAX);
+    }
+    distances.insert(start, 0);
+    
+    heap.push(State { cost: 0, position: start });
+    
+    while let Some(State { cost, position }) = heap.pop() {
+        if cost > distances[&position] {
+            continue;
+        }
+        
+        for &(neighbor, weight) in graph.neighbors(position) {
+            let next_cost = cost + weight;
+            
+            if next_cost < distances[&neighbor] {
+                distances.insert(neighbor, next_cost);
+                predecessors.insert(neighbor, position);
+                heap.push(State { cost: next_cost, position: neighbor });
+            }
+        }
+    }
+    
+    ShortestPathTree { source: start, distances, predecessors }
+}
+
+/// Dijkstra's algorithm from several sources at once (a virtual super-source
+/// with an edge of weight `offset` to every `(source, offset)` pair)
+pub fn multi_source_dijkstra(graph: &crate::graph::Graph, sources: &[(usize, usize)]) -> MultiSourceResult {
+    let mut result = MultiSourceResult::init(graph, sources);
+    let mut heap = BinaryHeap::new();
+    
+    for (&node, &cost) in &result.distances {
+        if cost != usize::MAX {
+            heap.push(State { cost, position: node });
+        }
+    }
+    
+    while let Some(State { cost, position }) = heap.pop() {
+        if cost > result.distances[&position] {
+            continue;
+        }
+        let source = result.nearest_source[&position];
+        
+        for &(neighbor, weight) in graph.neighbors(position) {
+            let next_cost = cost + weight;
+            
+            if next_cost < result.distance(neighbor) {
+                result.distances.insert(neighbor, next_cost);
+                result.nearest_source.insert(neighbor, source);
+                heap.push(State { cost: next_cost, position: neighbor });
+            }
+        }
+    }
+    
+    result
+}
+
+/// Point-to-point Dijkstra that stops as soon as `target` is settled.
+/// Returns the distance and the path, or `None` if `target` is unreachable.
+pub fn shortest_path(graph: &crate::graph::Graph, start: usize, target: usize) -> Option<(usize, Vec<usize>)> {
+    shortest_path_masked(graph, start, target, &GraphMask::new())
+}
+
+/// [`shortest_path`] ignoring the nodes and edges hidden by `mask`
+pub fn shortest_path_masked(
+    graph: &crate::graph::Graph,
+    start: usize,
+    target: usize,
+    mask: &GraphMask,
+) -> Option<(usize, Vec<usize>)> {
+    if mask.is_node_masked(start) {
+        return None;
+    }
+    
+    let mut distances = HashMap::new();
+    let mut predecessors = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    
+    distances.insert(start, 0);
+    heap.push(State { cost: 0, position: start });
+    
+    while let Some(State { cost, position }) = heap.pop() {
+        if cost > distances[&position] {
+            continue;
+        }
+        
+        if position == target {
+            let mut path = vec![target];
+            let mut current = target;
+            while let Some(&previous) = predecessors.get(&current) {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some((cost, path));
+        }
+        
+        for (neighbor, weight) in graph.neighbors_masked(position, mask) {
+            let next_cost = cost + weight;
+            
+            if next_cost < *distances.get(&neighbor).unwrap_or(&usize::MAX) {
+                distances.insert(neighbor, next_cost);
+                predecessors.insert(neighbor, position);
+                heap.push(State { cost: next_cost, position: neighbor });
+            }
+        }
+    }
+    
+    None
+}
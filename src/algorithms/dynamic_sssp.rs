@@ -0,0 +1,209 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::cmp::Ordering;
+use crate::algorithms::dijkstra::dijkstra_tree;
+use crate::algorithms::shortest_path_tree::ShortestPathTree;
+use crate::graph::Graph;
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+struct State {
+    cost: usize,
+    position: usize,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+            .then_with(|| self.position.cmp(&other.position))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Shortest-path tree from a fixed source, kept up to date as the graph changes.
+///
+/// Updates only touch the nodes whose distance actually changes (insertions and
+/// decreases) or the subtree hanging off a changed tree edge (deletions and
+/// increases) instead of rerunning Dijkstra from scratch.
+#[derive(Debug, Clone)]
+pub struct DynamicSssp {
+    graph: Graph,
+    // Incoming edges, needed to find new parents after a deletion
+    reverse: Graph,
+    tree: ShortestPathTree,
+}
+
+impl DynamicSssp {
+    pub fn new(graph: Graph, source: usize) -> Self {
+        let tree = dijkstra_tree(&graph, source);
+        let reverse = graph.reversed();
+        DynamicSssp { graph, reverse, tree }
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    pub fn tree(&self) -> &ShortestPathTree {
+        &self.tree
+    }
+
+    pub fn distance(&self, node: usize) -> usize {
+        self.tree.distance(node)
+    }
+
+    /// Add the edge `from -> to` (alongside any existing parallel edges).
+    /// Returns the number of nodes whose distance decreased.
+    pub fn insert_edge(&mut self, from: usize, to: usize, weight: usize) -> usize {
+        self.graph.add_edge(from, to, weight);
+        self.reverse.add_edge(to, from, weight);
+        self.relax_from(from, to, weight)
+    }
+
+    /// Lower the weight of `from -> to`. Does nothing unless `weight` is below
+    /// the current weight. Returns the number of nodes whose distance decreased.
+    pub fn decrease_weight(&mut self, from: usize, to: usize, weight: usize) -> usize {
+        match self.graph.edge_weight(from, to) {
+            Some(current) if weight < current => {
+                self.graph.set_edge_weight(from, to, weight);
+                self.reverse.set_edge_weight(to, from, weight);
+                self.relax_from(from, to, weight)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Remove every edge `from -> to`.
+    /// Returns the number of nodes whose distance increased.
+    pub fn delete_edge(&mut self, from: usize, to: usize) -> usize {
+        if self.graph.remove_edge(from, to).is_none() {
+            return 0;
+        }
+        self.reverse.remove_edge(to, from);
+        self.repair(from, to)
+    }
+
+    /// Raise the weight of `from -> to`. Does nothing unless `weight` is above
+    /// the current weight. Returns the number of nodes whose distance increased.
+    pub fn increase_weight(&mut self, from: usize, to: usize, weight: usize) -> usize {
+        match self.graph.edge_weight(from, to) {
+            Some(current) if weight > current => {
+                self.graph.set_edge_weight(from, to, weight);
+                self.reverse.set_edge_weight(to, from, weight);
+                self.repair(from, to)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Compare the maintained distances with a fresh Dijkstra run.
+    /// Returns `(node, maintained, recomputed)` for every disagreement.
+    pub fn verify(&self) -> Vec<(usize, usize, usize)> {
+        let fresh = dijkstra_tree(&self.graph, self.tree.source);
+
+        self.graph.nodes().iter()
+            .map(|&node| (node, self.tree.distance(node), fresh.distance(node)))
+            .filter(|&(_, maintained, recomputed)| maintained != recomputed)
+            .collect()
+    }
+
+    // A cheaper edge `from -> to` can only shorten paths through `to`, so run
+    // Dijkstra from `to` and stop expanding wherever nothing improves
+    fn relax_from(&mut self, from: usize, to: usize, weight: usize) -> usize {
+        let candidate = self.tree.distance(from).saturating_add(weight);
+        if candidate >= self.tree.distance(to) {
+            return 0;
+        }
+
+        let mut changed = 0;
+        let mut heap = BinaryHeap::new();
+        self.tree.distances.insert(to, candidate);
+        self.tree.predecessors.insert(to, from);
+        heap.push(State { cost: candidate, position: to });
+
+        while let Some(State { cost, position }) = heap.pop() {
+            if cost > self.tree.distance(position) {
+                continue;
+            }
+            changed += 1;
+
+            for &(neighbor, weight) in self.graph.neighbors(position) {
+                let next_cost = cost.saturating_add(weight);
+
+                if next_cost < self.tree.distance(neighbor) {
+                    self.tree.distances.insert(neighbor, next_cost);
+                    self.tree.predecessors.insert(neighbor, position);
+                    heap.push(State { cost: next_cost, position: neighbor });
+                }
+            }
+        }
+
+        changed
+    }
+
+    // Ramalingam–Reps style repair after `from -> to` got worse. Only a tree
+    // edge matters; then exactly the subtree below `to` may lose its paths.
+    fn repair(&mut self, from: usize, to: usize) -> usize {
+        if self.tree.predecessors.get(&to) != Some(&from) {
+            return 0;
+        }
+
+        // Collect the subtree of `to` by following tree edges downwards
+        let mut affected = HashSet::from([to]);
+        let mut stack = vec![to];
+        while let Some(node) = stack.pop() {
+            for &(child, _) in self.graph.neighbors(node) {
+                if self.tree.predecessors.get(&child) == Some(&node) && affected.insert(child) {
+                    stack.push(child);
+                }
+            }
+        }
+
+        let old: HashMap<usize, usize> = affected.iter().map(|&node| (node, self.tree.distance(node))).collect();
+
+        // Best entry into each affected node from the unaffected part of the tree
+        let mut heap = BinaryHeap::new();
+        for &node in &affected {
+            let mut best = (usize::MAX, None);
+            for &(parent, weight) in self.reverse.neighbors(node) {
+                if !affected.contains(&parent) {
+                    let candidate = self.tree.distance(parent).saturating_add(weight);
+                    if candidate < best.0 {
+                        best = (candidate, Some(parent));
+                    }
+                }
+            }
+
+            self.tree.distances.insert(node, best.0);
+            match best.1 {
+                Some(parent) => {
+                    self.tree.predecessors.insert(node, parent);
+                    heap.push(State { cost: best.0, position: node });
+                }
+                None => { self.tree.predecessors.remove(&node); }
+            }
+        }
+
+        // Dijkstra inside the affected region; distances elsewhere cannot change
+        while let Some(State { cost, position }) = heap.pop() {
+            if cost > self.tree.distance(position) {
+                continue;
+            }
+
+            for &(neighbor, weight) in self.graph.neighbors(position) {
+                let next_cost = cost.saturating_add(weight);
+
+                if affected.contains(&neighbor) && next_cost < self.tree.distance(neighbor) {
+                    self.tree.distances.insert(neighbor, next_cost);
+                    self.tree.predecessors.insert(neighbor, position);
+                    heap.push(State { cost: next_cost, position: neighbor });
+                }
+            }
+        }
+
+        old.iter().filter(|&(&node, &distance)| self.tree.distance(node) != distance).count()
+    }
+}
@@ -0,0 +1,274 @@
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+use std::cmp::Ordering;
+use std::rc::Rc;
+use crate::algorithms::dijkstra::{dijkstra, shortest_path, shortest_path_masked};
+use crate::graph::{Graph, GraphMask};
+
+/// Yen's algorithm: the `k` cheapest loopless paths from `source` to `target`.
+///
+/// Paths are returned as `(cost, nodes)` in increasing order of cost (ties
+/// broken by node sequence). Fewer than `k` paths are returned when the graph
+/// does not contain that many simple paths.
+pub fn yen_k_shortest_paths(graph: &Graph, source: usize, target: usize, k: usize) -> Vec<(usize, Vec<usize>)> {
+    let mut accepted: Vec<(usize, Vec<usize>)> = Vec::new();
+    if k == 0 {
+        return accepted;
+    }
+    match shortest_path(graph, source, target) {
+        Some(first) => accepted.push(first),
+        None => return accepted,
+    }
+
+    // Candidate paths ordered by (cost, nodes); `seen` stops duplicates
+    let mut candidates: BTreeSet<(usize, Vec<usize>)> = BTreeSet::new();
+    let mut seen: HashSet<Vec<usize>> = accepted.iter().map(|(_, path)| path.clone()).collect();
+    let mut mask = GraphMask::new();
+
+    while accepted.len() < k {
+        let (_, previous) = accepted.last().unwrap().clone();
+        let mut root_cost = 0;
+
+        // Deviate from the previous path at every node except the target
+        for i in 0..previous.len() - 1 {
+            let spur_node = previous[i];
+            let root = &previous[..=i];
+
+            mask.clear();
+            // Forbid continuing along any accepted path that shares this root
+            for (_, path) in &accepted {
+                if path.len() > i + 1 && &path[..=i] == root {
+                    mask.mask_edge(path[i], path[i + 1]);
+                }
+            }
+            // Keep the spur path loopless by hiding the rest of the root
+            for &node in &root[..i] {
+                mask.mask_node(node);
+            }
+
+            if let Some((spur_cost, spur_path)) = shortest_path_masked(graph, spur_node, target, &mask) {
+                let mut path = root[..i].to_vec();
+                path.extend(spur_path);
+                if seen.insert(path.clone()) {
+                    candidates.insert((root_cost + spur_cost, path));
+                }
+            }
+
+            root_cost += graph.edge_weight(spur_node, previous[i + 1]).unwrap();
+        }
+
+        match candidates.pop_first() {
+            Some(next) => accepted.push(next),
+            None => break,
+        }
+    }
+
+    accepted
+}
+
+/// Walk prefix stored as a linked list so extensions share their prefix
+struct Prefix {
+    node: usize,
+    parent: Option<Rc<Prefix>>,
+}
+
+impl Prefix {
+    fn contains(&self, node: usize) -> bool {
+        let mut current = Some(self);
+        while let Some(prefix) = current {
+            if prefix.node == node {
+                return true;
+            }
+            current = prefix.parent.as_deref();
+        }
+        false
+    }
+
+    fn to_path(&self) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut current = Some(self);
+        while let Some(prefix) = current {
+            path.push(prefix.node);
+            current = prefix.parent.as_deref();
+        }
+        path.reverse();
+        path
+    }
+}
+
+struct State {
+    estimate: usize,
+    at_target: bool,
+    hops: usize,
+    cost: usize,
+    prefix: Rc<Prefix>,
+}
+
+impl PartialEq for State {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for State {}
+
+// Among equal estimates, finished walks come first and then shorter prefixes.
+// Zero-weight cycles keep the estimate constant, so ordering by cost alone
+// could extend such a cycle forever without ever popping a finished walk.
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+            .then_with(|| self.at_target.cmp(&other.at_target))
+            .then_with(|| other.hops.cmp(&self.hops))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Lazy enumeration of `source -> target` walks in nondecreasing cost order.
+///
+/// A best-first (A*) search over walk prefixes, in the spirit of K*: a
+/// reverse Dijkstra from the target gives the exact remaining distance of
+/// every node, each prefix is keyed by its cost plus that distance, and
+/// popping a prefix that ends at the target yields the next walk. The
+/// heuristic is exact, so no prefix costing more than the walk being
+/// returned is ever expanded.
+///
+/// There is no sidetrack heap: every popped prefix pushes one extension per
+/// outgoing edge, so the heap holds all prefixes generated so far and grows
+/// with the number of expanded prefixes (up to `k` times the walk length
+/// times the out-degree after `k` walks), not with `k` alone. Walks may
+/// revisit nodes (including the target); use [`PathEnumerator::simple_paths`]
+/// for loopless paths only.
+///
+/// Parallel edges count once, at their minimum weight, so every node sequence
+/// is yielded at most once and its cost matches [`Graph::edge_weight`].
+///
+/// Every call to `next` returns after finitely many steps, also on graphs
+/// with zero-weight cycles. The sequence itself is unbounded whenever a cycle
+/// lies on some `source -> target` walk; with a cost bound it still is if
+/// that cycle has weight zero.
+pub struct PathEnumerator<'a> {
+    graph: &'a Graph,
+    target: usize,
+    to_target: HashMap<usize, usize>,
+    heap: BinaryHeap<State>,
+    max_cost: usize,
+    simple_only: bool,
+}
+
+impl<'a> PathEnumerator<'a> {
+    pub fn new(graph: &'a Graph, source: usize, target: usize) -> Self {
+        let to_target: HashMap<usize, usize> = dijkstra(&graph.reversed(), target)
+            .into_iter()
+            .filter(|&(_, distance)| distance != usize::MAX)
+            .collect();
+
+        let mut heap = BinaryHeap::new();
+        if let Some(&estimate) = to_target.get(&source) {
+            heap.push(State {
+                estimate,
+                at_target: source == target,
+                hops: 0,
+                cost: 0,
+                prefix: Rc::new(Prefix { node: source, parent: None }),
+            });
+        }
+
+        PathEnumerator {
+            graph,
+            target,
+            to_target,
+            heap,
+            max_cost: usize::MAX,
+            simple_only: false,
+        }
+    }
+
+    /// Only yield walks that cost at most `max_cost`
+    pub fn with_max_cost(mut self, max_cost: usize) -> Self {
+        self.max_cost = max_cost;
+        self
+    }
+
+    /// Only yield paths that never repeat a node
+    pub fn simple_paths(mut self) -> Self {
+        self.simple_only = true;
+        self
+    }
+
+    /// Cost of the shortest walk, `None` if `target` is unreachable
+    pub fn shortest_cost(&self) -> Option<usize> {
+        self.heap.peek().map(|state| state.estimate)
+    }
+}
+
+impl Iterator for PathEnumerator<'_> {
+    type Item = (usize, Vec<usize>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(State { estimate, at_target, hops, cost, prefix }) = self.heap.pop() {
+            if estimate > self.max_cost {
+                self.heap.clear();
+                return None;
+            }
+
+            if !(at_target && self.simple_only) {
+                for (neighbor, weight) in cheapest_edges(self.graph, prefix.node) {
+                    let Some(&remaining) = self.to_target.get(&neighbor) else { continue };
+                    if self.simple_only && prefix.contains(neighbor) {
+                        continue;
+                    }
+                    let next_cost = cost.saturating_add(weight);
+                    self.heap.push(State {
+                        estimate: next_cost.saturating_add(remaining),
+                        at_target: neighbor == self.target,
+                        hops: hops + 1,
+                        cost: next_cost,
+                        prefix: Rc::new(Prefix { node: neighbor, parent: Some(prefix.clone()) }),
+                    });
+                }
+            }
+
+            if at_target {
+                return Some((cost, prefix.to_path()));
+            }
+        }
+
+        None
+    }
+}
+
+// Outgoing edges of `node` with parallel edges collapsed to the cheapest one
+fn cheapest_edges(graph: &Graph, node: usize) -> Vec<(usize, usize)> {
+    let mut edges = graph.neighbors(node).to_vec();
+    edges.sort_unstable();
+    edges.dedup_by_key(|&mut (neighbor, _)| neighbor);
+    edges
+}
+
+/// All `source -> target` paths costing at most `(1 + epsilon)` times the
+/// optimum, cheapest first. Set `simple_only` to skip walks that repeat nodes.
+pub fn paths_within_factor(
+    graph: &Graph,
+    source: usize,
+    target: usize,
+    epsilon: f64,
+    simple_only: bool,
+) -> PathEnumerator<'_> {
+    let mut enumerator = PathEnumerator::new(graph, source, target);
+    if simple_only {
+        enumerator = enumerator.simple_paths();
+    }
+
+    match enumerator.shortest_cost() {
+        Some(optimum) => {
+            let bound = (optimum as f64 * (1.0 + epsilon)).floor() as usize;
+            enumerator.with_max_cost(bound)
+        }
+        None => enumerator,
+    }
+}
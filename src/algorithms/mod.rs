@@ -1,6 +1,35 @@
 pub mod dijkstra;
 pub mod bfs;
 pub mod bounded_multi_source_shortest_path;
+pub mod bidirectional_dijkstra;
+pub mod astar;
+pub mod alt;
+pub mod contraction_hierarchies;
+pub mod multi_source;
+pub mod k_shortest_paths;
+pub mod zero_one_bfs;
+pub mod dial;
+pub mod registry;
+pub mod dynamic_sssp;
+pub mod workspace;
+pub mod batch;
+pub mod components;
+pub mod shortest_path_tree;
 
-pub use dijkstra::dijkstra;
-pub use bfs::bfs;
+pub use dijkstra::{dijkstra, dijkstra_tree, multi_source_dijkstra, shortest_path, shortest_path_masked};
+pub use bfs::{bfs, multi_source_bfs};
+pub use bounded_multi_source_shortest_path::{bmssp, bmssp_with_workspace, bounded_msssp, bounded_msssp_at_level, multi_source_bmssp};
+pub use bidirectional_dijkstra::{bidirectional_dijkstra, bidirectional_dijkstra_with_reverse};
+pub use astar::{astar, check_heuristic, EuclideanHeuristic, Heuristic, HeuristicReport, ManhattanHeuristic};
+pub use alt::{alt_query, AltHeuristic, LandmarkStrategy, Landmarks};
+pub use contraction_hierarchies::ContractionHierarchy;
+pub use zero_one_bfs::{unit_bfs, zero_one_bfs};
+pub use dial::dial;
+pub use registry::{solve, Solver};
+pub use k_shortest_paths::{paths_within_factor, yen_k_shortest_paths, PathEnumerator};
+pub use dynamic_sssp::DynamicSssp;
+pub use workspace::{bfs_with_workspace, dijkstra_with_workspace, QueryWorkspace};
+pub use components::{reachability, strongly_connected_components, weakly_connected_components, ReachabilityReport};
+pub use batch::{batch_distances, batch_for_each, DistanceMatrix};
+pub use multi_source::{zero_offsets, MultiSourceResult};
+pub use shortest_path_tree::ShortestPathTree;
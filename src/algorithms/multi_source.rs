@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Result of a multi-source shortest path computation.
+///
+/// Every node of the graph has an entry in `distances` (`usize::MAX` if no
+/// source reaches it); `nearest_source` only holds reached nodes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiSourceResult {
+    pub distances: HashMap<usize, usize>,
+    pub nearest_source: HashMap<usize, usize>,
+}
+
+impl MultiSourceResult {
+    /// Start state for `sources` given as `(node, initial offset)` pairs.
+    /// A node listed more than once keeps its smallest offset.
+    pub(crate) fn init(graph: &crate::graph::Graph, sources: &[(usize, usize)]) -> Self {
+        let mut result = MultiSourceResult::default();
+
+        for &node in graph.nodes() {
+            result.distances.insert(node, usize::MAX);
+        }
+        for &(source, offset) in sources {
+            if offset < *result.distances.get(&source).unwrap_or(&usize::MAX) {
+                result.distances.insert(source, offset);
+                result.nearest_source.insert(source, source);
+            }
+        }
+
+        result
+    }
+
+    pub fn distance(&self, node: usize) -> usize {
+        *self.distances.get(&node).unwrap_or(&usize::MAX)
+    }
+
+    pub fn nearest_source(&self, node: usize) -> Option<usize> {
+        self.nearest_source.get(&node).copied()
+    }
+}
+
+/// Convenience for sources that all start at distance 0
+pub fn zero_offsets(sources: &[usize]) -> Vec<(usize, usize)> {
+    sources.iter().map(|&source| (source, 0)).collect()
+}
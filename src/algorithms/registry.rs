@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use crate::algorithms::{bfs, bmssp, dial, dijkstra, unit_bfs, zero_one_bfs};
+use crate::graph::Graph;
+
+/// Largest edge weight for which [`Solver::select`] prefers Dial's buckets over a heap
+pub const DIAL_MAX_WEIGHT: usize = 256;
+
+/// Single-source solvers that can be chosen by name or picked automatically
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Solver {
+    Dijkstra,
+    /// Label-correcting search ([`bfs`])
+    LabelCorrecting,
+    Bmssp,
+    /// Plain BFS, only valid when every weight is 1
+    UnitBfs,
+    /// Only valid when every weight is 0 or 1
+    ZeroOneBfs,
+    Dial,
+}
+
+impl Solver {
+    pub const ALL: [Solver; 6] = [
+        Solver::Dijkstra,
+        Solver::LabelCorrecting,
+        Solver::Bmssp,
+        Solver::UnitBfs,
+        Solver::ZeroOneBfs,
+        Solver::Dial,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Solver::Dijkstra => "Dijkstra",
+            Solver::LabelCorrecting => "Label-correcting",
+            Solver::Bmssp => "BMSSP",
+            Solver::UnitBfs => "BFS",
+            Solver::ZeroOneBfs => "0-1 BFS",
+            Solver::Dial => "Dial",
+        }
+    }
+
+    /// Whether this solver gives exact distances on `graph`
+    pub fn supports(&self, graph: &Graph) -> bool {
+        match (self, graph.weight_range()) {
+            (Solver::UnitBfs, Some((min, max))) => min == 1 && max == 1,
+            (Solver::ZeroOneBfs, Some((_, max))) => max <= 1,
+            _ => true,
+        }
+    }
+
+    /// Pick the cheapest solver that is exact for `graph`'s weight range
+    pub fn select(graph: &Graph) -> Solver {
+        match graph.weight_range() {
+            None | Some((1, 1)) => Solver::UnitBfs,
+            Some((_, max)) if max <= 1 => Solver::ZeroOneBfs,
+            Some((_, max)) if max <= DIAL_MAX_WEIGHT => Solver::Dial,
+            _ => Solver::Dijkstra,
+        }
+    }
+
+    pub fn run(&self, graph: &Graph, start: usize) -> HashMap<usize, usize> {
+        match self {
+            Solver::Dijkstra => dijkstra(graph, start),
+            Solver::LabelCorrecting => bfs(graph, start),
+            Solver::Bmssp => bmssp(graph, start),
+            Solver::UnitBfs => unit_bfs(graph, start),
+            Solver::ZeroOneBfs => zero_one_bfs(graph, start),
+            Solver::Dial => dial(graph, start),
+        }
+    }
+}
+
+/// Single-source distances with the solver chosen by [`Solver::select`]
+pub fn solve(graph: &Graph, start: usize) -> HashMap<usize, usize> {
+    Solver::select(graph).run(graph, start)
+}
@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Distances from a single source together with the predecessor of every reached node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShortestPathTree {
+    pub source: usize,
+    pub distances: HashMap<usize, usize>,
+    pub predecessors: HashMap<usize, usize>,
+}
+
+impl ShortestPathTree {
+    /// Distance from the source, `usize::MAX` if unreachable
+    pub fn distance(&self, node: usize) -> usize {
+        *self.distances.get(&node).unwrap_or(&usize::MAX)
+    }
+
+    /// Path from the source to `node` (inclusive), or `None` if unreachable
+    pub fn path_to(&self, node: usize) -> Option<Vec<usize>> {
+        if self.distance(node) == usize::MAX {
+            return None;
+        }
+
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(&previous) = self.predecessors.get(&current) {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Children lists of the tree, i.e. the predecessor map inverted
+    pub fn children(&self) -> HashMap<usize, Vec<usize>> {
+        let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+        for (&node, &parent) in &self.predecessors {
+            children.entry(parent).or_default().push(node);
+        }
+        children
+    }
+}
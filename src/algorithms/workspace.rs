@@ -0,0 +1,233 @@
+use std::collections::{BinaryHeap, HashMap};
+use std::cmp::Ordering;
+use crate::graph::Graph;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct State {
+    cost: usize,
+    slot: usize,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.cmp(&self.cost)
+            .then_with(|| self.slot.cmp(&other.slot))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Read/write access to tentative distances keyed by node id
+pub(crate) trait DistanceMap {
+    fn get_distance(&self, node: usize) -> usize;
+    fn set_distance(&mut self, node: usize, distance: usize);
+}
+
+impl DistanceMap for HashMap<usize, usize> {
+    fn get_distance(&self, node: usize) -> usize {
+        *HashMap::get(self, &node).unwrap_or(&usize::MAX)
+    }
+
+    fn set_distance(&mut self, node: usize, distance: usize) {
+        self.insert(node, distance);
+    }
+}
+
+/// Scratch space for repeated single-source queries on one graph.
+///
+/// Nodes are mapped to dense slots once, and the distance array is reset
+/// lazily: each query bumps a timestamp, and a slot whose stamp is stale reads
+/// as unreached. A query therefore costs nothing for the nodes it never touches.
+/// The workspace describes the graph at the time it was created; build a new
+/// one after mutating the graph.
+#[derive(Debug, Clone)]
+pub struct QueryWorkspace {
+    slots: SlotGraph,
+    scratch: Scratch,
+}
+
+/// The graph renumbered to dense slots. Read-only once built, so concurrent
+/// queries can share one copy and keep only a [`Scratch`] each.
+#[derive(Debug, Clone)]
+pub(crate) struct SlotGraph {
+    node_ids: Vec<usize>,
+    index: HashMap<usize, usize>,
+    // Adjacency by slot, so queries never hash node ids
+    adjacency: Vec<Vec<(usize, usize)>>,
+}
+
+/// Per-query state over the slots of a [`SlotGraph`]
+#[derive(Debug, Clone)]
+pub(crate) struct Scratch {
+    distances: Vec<usize>,
+    stamps: Vec<u32>,
+    current: u32,
+    heap: BinaryHeap<State>,
+    stack: Vec<usize>,
+}
+
+impl SlotGraph {
+    pub(crate) fn new(graph: &Graph) -> Self {
+        let node_ids: Vec<usize> = graph.nodes().clone();
+        let index: HashMap<usize, usize> = node_ids.iter().enumerate().map(|(slot, &node)| (node, slot)).collect();
+        let adjacency = node_ids.iter()
+            .map(|&node| graph.neighbors(node).iter().map(|&(to, weight)| (index[&to], weight)).collect())
+            .collect();
+
+        SlotGraph { node_ids, index, adjacency }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.node_ids.len()
+    }
+
+    pub(crate) fn slot(&self, node: usize) -> Option<usize> {
+        self.index.get(&node).copied()
+    }
+}
+
+impl Scratch {
+    pub(crate) fn new(slots: &SlotGraph) -> Self {
+        Scratch {
+            distances: vec![usize::MAX; slots.len()],
+            stamps: vec![0; slots.len()],
+            current: 0,
+            heap: BinaryHeap::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    // Start a new query: invalidate every slot in O(1)
+    fn reset(&mut self) {
+        if self.current == u32::MAX {
+            self.stamps.fill(0);
+            self.current = 0;
+        }
+        self.current += 1;
+        self.heap.clear();
+        self.stack.clear();
+    }
+
+    /// Distance of `slot` in the last query, `usize::MAX` if unreached
+    pub(crate) fn slot_distance(&self, slot: usize) -> usize {
+        if self.stamps[slot] == self.current { self.distances[slot] } else { usize::MAX }
+    }
+
+    fn set_slot(&mut self, slot: usize, distance: usize) {
+        self.stamps[slot] = self.current;
+        self.distances[slot] = distance;
+    }
+}
+
+impl QueryWorkspace {
+    pub fn new(graph: &Graph) -> Self {
+        let slots = SlotGraph::new(graph);
+        let scratch = Scratch::new(&slots);
+        QueryWorkspace { slots, scratch }
+    }
+
+    /// Distance from the last query's source, `usize::MAX` if unreached or unknown
+    pub fn distance(&self, node: usize) -> usize {
+        self.slots.slot(node).map_or(usize::MAX, |slot| self.scratch.slot_distance(slot))
+    }
+
+    /// Nodes reached by the last query with their distances
+    pub fn reached(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.slots.len())
+            .filter(|&slot| self.scratch.slot_distance(slot) != usize::MAX)
+            .map(|slot| (self.slots.node_ids[slot], self.scratch.distances[slot]))
+    }
+
+    /// The last query's result in the same form as [`crate::dijkstra`] returns it
+    pub fn distances(&self) -> HashMap<usize, usize> {
+        (0..self.slots.len())
+            .map(|slot| (self.slots.node_ids[slot], self.scratch.slot_distance(slot)))
+            .collect()
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.scratch.reset();
+    }
+}
+
+impl DistanceMap for QueryWorkspace {
+    fn get_distance(&self, node: usize) -> usize {
+        self.distance(node)
+    }
+
+    fn set_distance(&mut self, node: usize, distance: usize) {
+        if let Some(slot) = self.slots.slot(node) {
+            self.scratch.set_slot(slot, distance);
+        }
+    }
+}
+
+impl<T: DistanceMap> DistanceMap for &mut T {
+    fn get_distance(&self, node: usize) -> usize {
+        (**self).get_distance(node)
+    }
+
+    fn set_distance(&mut self, node: usize, distance: usize) {
+        (**self).set_distance(node, distance)
+    }
+}
+
+/// [`crate::dijkstra`] reusing `workspace`, which must have been built from
+/// `graph`; read the result from the workspace
+pub fn dijkstra_with_workspace(graph: &Graph, start: usize, workspace: &mut QueryWorkspace) {
+    debug_assert_eq!(graph.node_count(), workspace.slots.len(), "workspace built for a different graph");
+    dijkstra_slots(&workspace.slots, start, &mut workspace.scratch);
+}
+
+/// Dijkstra from node `start` over shared `slots`, leaving the result in `scratch`
+pub(crate) fn dijkstra_slots(slots: &SlotGraph, start: usize, scratch: &mut Scratch) {
+    scratch.reset();
+    let Some(start) = slots.slot(start) else { return };
+
+    scratch.set_slot(start, 0);
+    scratch.heap.push(State { cost: 0, slot: start });
+
+    while let Some(State { cost, slot }) = scratch.heap.pop() {
+        if cost > scratch.slot_distance(slot) {
+            continue;
+        }
+
+        for &(neighbor, weight) in &slots.adjacency[slot] {
+            let next_cost = cost + weight;
+
+            if next_cost < scratch.slot_distance(neighbor) {
+                scratch.set_slot(neighbor, next_cost);
+                scratch.heap.push(State { cost: next_cost, slot: neighbor });
+            }
+        }
+    }
+}
+
+/// [`crate::bfs`] (label-correcting) reusing `workspace`, which must have been
+/// built from `graph`; read the result from the workspace
+pub fn bfs_with_workspace(graph: &Graph, start: usize, workspace: &mut QueryWorkspace) {
+    debug_assert_eq!(graph.node_count(), workspace.slots.len(), "workspace built for a different graph");
+    let QueryWorkspace { slots, scratch } = workspace;
+    scratch.reset();
+    let Some(start) = slots.slot(start) else { return };
+
+    scratch.set_slot(start, 0);
+    scratch.stack.push(start);
+
+    while let Some(current) = scratch.stack.pop() {
+        let distance = scratch.slot_distance(current);
+
+        for &(neighbor, weight) in &slots.adjacency[current] {
+            let new_distance = distance.saturating_add(weight);
+
+            if new_distance < scratch.slot_distance(neighbor) {
+                scratch.set_slot(neighbor, new_distance);
+                scratch.stack.push(neighbor);
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Breadth-first search counting every edge as weight 1, whatever its stored weight
+pub fn unit_bfs(graph: &crate::graph::Graph, start: usize) -> HashMap<usize, usize> {
+    let mut distances = HashMap::new();
+
+    for &node in graph.nodes() {
+        distances.insert(node, usize::MAX);
+    }
+    distances.insert(start, 0);
+
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(current) = queue.pop_front() {
+        let next_distance = distances[&current] + 1;
+
+        for &(neighbor, _) in graph.neighbors(current) {
+            if distances[&neighbor] == usize::MAX {
+                distances.insert(neighbor, next_distance);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    distances
+}
+
+/// 0-1 BFS: shortest paths when every weight is 0 or 1, using a deque instead
+/// of a heap (0-edges go to the front, 1-edges to the back)
+pub fn zero_one_bfs(graph: &crate::graph::Graph, start: usize) -> HashMap<usize, usize> {
+    let mut distances = HashMap::new();
+
+    for &node in graph.nodes() {
+        distances.insert(node, usize::MAX);
+    }
+    distances.insert(start, 0);
+
+    let mut deque = VecDeque::from([(0, start)]);
+
+    while let Some((cost, current)) = deque.pop_front() {
+        if cost > distances[&current] {
+            continue;
+        }
+
+        for &(neighbor, weight) in graph.neighbors(current) {
+            debug_assert!(weight <= 1, "zero_one_bfs requires weights 0 or 1, got {}", weight);
+            let next_cost = cost + weight;
+
+            if next_cost < distances[&neighbor] {
+                distances.insert(neighbor, next_cost);
+                if weight == 0 {
+                    deque.push_front((next_cost, neighbor));
+                } else {
+                    deque.push_back((next_cost, neighbor));
+                }
+            }
+        }
+    }
+
+    distances
+}
@@ -1,10 +1,18 @@
-use sssp_algos::{dijkstra, bfs, generate_random_graph, BenchmarkResult};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use sssp_algos::{dijkstra, bfs, generate_random_graph_with_rng, seed_from_env, BenchmarkResult, SEED_ENV_VAR};
 use std::time::Instant;
-use std::iter::repeat;
 fn main() {
     println!("SSSP Algorithms Benchmark");
     println!("========================");
     
+    // Seed from the first argument, then the environment, then at random
+    let seed = std::env::args().nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or_else(seed_from_env);
+    println!("Seed: {} (replay with `benchmark {}` or {}={})", seed, seed, SEED_ENV_VAR, seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+    
     let graph_sizes = [100, 500, 1000];
     let edge_density = 0.3;
     let start_node = 0;
@@ -14,7 +22,7 @@ fn main() {
         let border: String = "_".repeat(40);
         println!("{}",border);
         
-        let graph = generate_random_graph(size, edge_density);
+        let graph = generate_random_graph_with_rng(size, edge_density, &mut rng);
         println!("Generated graph: {} nodes, {} edges", 
                 graph.node_count(), graph.edge_count());
         
@@ -0,0 +1,65 @@
+use sssp_algos::io::{load_edge_list, load_gr, save_csr, CsrGraph, EdgeListOptions};
+use std::process;
+use std::time::Instant;
+
+const USAGE: &str = "usage: convert <input.gr | edge list> <output.csr> [--undirected] [--weight-column N] [--delimiter C]
+
+Files ending in .gr are read as DIMACS graphs; anything else as an edge list
+with `#` comments and remapped labels (columns are 0-based).";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Err(message) = run(&args) {
+        eprintln!("error: {}\n\n{}", message, USAGE);
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let mut paths = Vec::new();
+    let mut options = EdgeListOptions::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--undirected" => options.directed = false,
+            "--weight-column" => {
+                let column = args.next().and_then(|value| value.parse().ok()).ok_or("--weight-column needs a number")?;
+                options.weight_column = Some(column);
+            }
+            "--delimiter" => {
+                let delimiter = args.next().and_then(|value| value.chars().next()).ok_or("--delimiter needs a character")?;
+                options.delimiter = Some(delimiter);
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            other if other.starts_with("--") => return Err(format!("unknown option `{}`", other)),
+            path => paths.push(path),
+        }
+    }
+    let [input, output] = paths[..] else {
+        return Err("expected an input and an output path".to_string());
+    };
+
+    let start = Instant::now();
+    let graph = if input.ends_with(".gr") {
+        load_gr(input)
+    } else {
+        load_edge_list(input, &options).map(|(graph, _)| graph)
+    }
+    .map_err(|error| format!("reading {}: {}", input, error))?;
+    println!("Read {} nodes, {} edges in {:?}", graph.node_count(), graph.edge_count(), start.elapsed());
+
+    let start = Instant::now();
+    save_csr(&graph, output).map_err(|error| format!("writing {}: {}", output, error))?;
+    println!("Wrote {} in {:?}", output, start.elapsed());
+
+    // Map the result back as a check and to show the load time
+    let start = Instant::now();
+    let csr = CsrGraph::open(output).map_err(|error| format!("verifying {}: {}", output, error))?;
+    println!("Verified {} nodes, {} edges, mapped in {:?}", csr.node_count(), csr.edge_count(), start.elapsed());
+
+    Ok(())
+}
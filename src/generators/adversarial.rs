@@ -0,0 +1,133 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::generators::weights::WeightDistribution;
+use crate::graph::Graph;
+
+// Hard inputs for specific algorithms. Every family is meant to be searched
+// from node 0, and the docs name the algorithm and the cost each one drives up.
+
+/// Targets **Dijkstra's** heap: every settled node lowers the key of each of
+/// its next `fan` successors, so the search performs about `nodes · fan`
+/// decrease-keys (heap pushes in this crate's lazy-deletion heaps) against
+/// `nodes` settles. With `fan = nodes` this is the Θ(n²) worst case on a
+/// complete DAG. Label-correcting search ([`crate::bfs`]) fares far worse:
+/// it keeps revisiting the chain as each shortcut is undercut by the next.
+///
+/// Node `i` has an edge of weight 1 to `i + 1` and of weight `2·(j − i)` to
+/// every `j` up to `i + fan`. Through `i` the tentative distance of `j` is
+/// `2j − i`, which strictly drops with every later `i`, while the true
+/// distance of `j` is `j` along the chain.
+pub fn generate_decrease_key_graph(nodes: usize, fan: usize) -> Graph {
+    let mut graph = Graph::new();
+    for i in 0..nodes {
+        graph.add_node(i);
+    }
+
+    for i in 0..nodes {
+        for j in i + 1..=(i + fan.max(1)).min(nodes.saturating_sub(1)) {
+            graph.add_edge(i, j, if j == i + 1 { 1 } else { 2 * (j - i) });
+        }
+    }
+
+    graph
+}
+
+/// Targets **BMSSP**'s recursion: the shortest-path tree is a single path
+/// of depth `nodes − 1`, the deepest possible, so every level of the
+/// recursion sees a frontier that advances by a bounded number of hops and
+/// `FindPivots` finds no wide subtrees to split the work. Each node also has
+/// shortcuts to `i + 2, i + 4, i + 8, …` (up to `shortcuts` of them) that are
+/// exactly one unit too expensive; they are relaxed at every level and keep
+/// tentative distances close to, but above, the final ones.
+pub fn generate_chain_with_shortcuts(nodes: usize, shortcuts: usize) -> Graph {
+    let mut graph = Graph::new();
+    for i in 0..nodes {
+        graph.add_node(i);
+    }
+
+    for i in 0..nodes.saturating_sub(1) {
+        graph.add_edge(i, i + 1, 1);
+
+        let mut span = 2;
+        for _ in 0..shortcuts {
+            if i + span >= nodes {
+                break;
+            }
+            graph.add_edge(i, i + span, span + 1);
+            span *= 2;
+        }
+    }
+
+    graph
+}
+
+/// Targets **label-correcting search** ([`crate::bfs`]) and BMSSP's bounded
+/// Bellman–Ford steps: `layers` layers of `width` nodes with complete
+/// bipartite edges between consecutive layers, fed by node 0. Weights come
+/// from `weights` plus a penalty of `index + 1` on edges leaving the node with
+/// that index in its layer. A LIFO search expands the last-pushed, most
+/// penalised node first, runs ahead along expensive paths and then has to
+/// correct every later layer again as cheaper tails are popped.
+/// Layer `l` holds nodes `1 + l·width ..= (l + 1)·width`.
+pub fn generate_layered_graph(layers: usize, width: usize, weights: &WeightDistribution) -> Graph {
+    generate_layered_graph_with_rng(layers, width, weights, &mut rand::thread_rng())
+}
+
+/// [`generate_layered_graph`] with a fixed seed
+pub fn generate_layered_graph_seeded(layers: usize, width: usize, weights: &WeightDistribution, seed: u64) -> Graph {
+    generate_layered_graph_with_rng(layers, width, weights, &mut StdRng::seed_from_u64(seed))
+}
+
+/// [`generate_layered_graph`] drawing from `rng`
+pub fn generate_layered_graph_with_rng<R: Rng + ?Sized>(
+    layers: usize,
+    width: usize,
+    weights: &WeightDistribution,
+    rng: &mut R,
+) -> Graph {
+    let mut graph = Graph::new();
+    graph.add_node(0);
+    if layers == 0 {
+        return graph;
+    }
+    let node = |layer: usize, index: usize| 1 + layer * width + index;
+
+    for index in 0..width {
+        graph.add_edge(0, node(0, index), weights.sample(rng));
+    }
+
+    for layer in 0..layers.saturating_sub(1) {
+        for from in 0..width {
+            for to in 0..width {
+                let weight = weights.sample(rng) + from + 1;
+                graph.add_edge(node(layer, from), node(layer + 1, to), weight);
+            }
+        }
+    }
+
+    graph
+}
+
+/// Targets the **tie handling** that BMSSP's Assumption 2.1 (all path lengths
+/// distinct) assumes away: a `width × height` grid where every edge weighs
+/// 1, so the number of shortest paths to a node grows exponentially with its
+/// distance and every distance level is one large plateau of equal keys.
+/// Node `(x, y)` is `y · width + x`.
+pub fn generate_tie_grid(width: usize, height: usize) -> Graph {
+    let mut graph = Graph::new();
+
+    for y in 0..height {
+        for x in 0..width {
+            let node = y * width + x;
+            graph.add_node(node);
+            if x + 1 < width {
+                graph.add_bidirectional_edge(node, node + 1, 1);
+            }
+            if y + 1 < height {
+                graph.add_bidirectional_edge(node, node + width, 1);
+            }
+        }
+    }
+
+    graph
+}
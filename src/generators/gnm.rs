@@ -0,0 +1,103 @@
+use std::collections::HashSet;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::generators::weights::WeightDistribution;
+use crate::graph::Graph;
+
+/// Number of distinct edges a simple graph on `nodes` nodes can have
+fn max_edges(nodes: usize, directed: bool) -> usize {
+    let pairs = nodes.saturating_mul(nodes.saturating_sub(1));
+    if directed { pairs } else { pairs / 2 }
+}
+
+/// Edge count giving an average out-degree of `degree` (directed) or an
+/// average degree of `degree` (undirected)
+pub fn edges_for_average_degree(nodes: usize, degree: f64, directed: bool) -> usize {
+    let edges = nodes as f64 * degree;
+    (if directed { edges } else { edges / 2.0 }).round() as usize
+}
+
+/// Erdős–Rényi G(n, m): `edges` distinct edges chosen uniformly among all
+/// node pairs, without self-loops, weights drawn from `weights`. An undirected edge is
+/// stored in both directions and counts once. Asking for more edges than
+/// exist yields the complete graph.
+///
+/// Sparse graphs (up to half of all pairs) are sampled by rejection in
+/// expected O(n + m); denser ones pick the pairs to leave out instead.
+pub fn generate_gnm_graph(nodes: usize, edges: usize, directed: bool, weights: &WeightDistribution) -> Graph {
+    generate_gnm_graph_with_rng(nodes, edges, directed, weights, &mut rand::thread_rng())
+}
+
+/// [`generate_gnm_graph`] with a fixed seed
+pub fn generate_gnm_graph_seeded(nodes: usize, edges: usize, directed: bool, weights: &WeightDistribution, seed: u64) -> Graph {
+    generate_gnm_graph_with_rng(nodes, edges, directed, weights, &mut StdRng::seed_from_u64(seed))
+}
+
+/// [`generate_gnm_graph`] drawing from `rng`
+pub fn generate_gnm_graph_with_rng<R: Rng + ?Sized>(
+    nodes: usize,
+    edges: usize,
+    directed: bool,
+    weights: &WeightDistribution,
+    rng: &mut R,
+) -> Graph {
+    let mut graph = Graph::new();
+    for i in 0..nodes {
+        graph.add_node(i);
+    }
+
+    let total = max_edges(nodes, directed);
+    let edges = edges.min(total);
+
+    let add = |graph: &mut Graph, from: usize, to: usize, rng: &mut R| {
+        let weight = weights.sample(rng);
+        if directed {
+            graph.add_edge(from, to, weight);
+        } else {
+            graph.add_bidirectional_edge(from, to, weight);
+        }
+    };
+
+    if edges <= total / 2 {
+        for (from, to) in sample_pairs(nodes, edges, directed, rng) {
+            add(&mut graph, from, to, rng);
+        }
+    } else {
+        // The output is already quadratic, so walking every pair costs nothing extra
+        let excluded: HashSet<(usize, usize)> = sample_pairs(nodes, total - edges, directed, rng).into_iter().collect();
+        for from in 0..nodes {
+            let targets = if directed { 0..nodes } else { from + 1..nodes };
+            for to in targets {
+                if from != to && !excluded.contains(&(from, to)) {
+                    add(&mut graph, from, to, rng);
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+// `count` distinct pairs without self-loops, normalized to `from < to` when
+// undirected, in draw order so a seed always gives the same graph. Callers
+// keep `count` at most half of all pairs, so each draw is accepted with
+// probability at least 1/2.
+fn sample_pairs<R: Rng + ?Sized>(nodes: usize, count: usize, directed: bool, rng: &mut R) -> Vec<(usize, usize)> {
+    let mut seen = HashSet::with_capacity(count);
+    let mut pairs = Vec::with_capacity(count);
+
+    while pairs.len() < count {
+        let from = rng.gen_range(0..nodes);
+        let to = rng.gen_range(0..nodes);
+        if from == to {
+            continue;
+        }
+
+        let pair = if directed || from < to { (from, to) } else { (to, from) };
+        if seen.insert(pair) {
+            pairs.push(pair);
+        }
+    }
+
+    pairs
+}
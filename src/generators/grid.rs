@@ -0,0 +1,200 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use crate::graph::Graph;
+
+/// Shape and weighting of a generated grid
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridOptions {
+    /// Also connect diagonal neighbours (8-neighbourhood in 2D, 26 in 3D)
+    pub diagonals: bool,
+    /// Probability that a cell is an obstacle and left out of the graph
+    pub obstacle_fraction: f64,
+    /// Weight of an axis-aligned step; diagonal steps cost √2 or √3 times as much
+    pub base_weight: usize,
+    /// Each edge weight is scaled by a uniform factor in `1 ± perturbation`
+    pub perturbation: f64,
+}
+
+impl Default for GridOptions {
+    fn default() -> Self {
+        GridOptions {
+            diagonals: false,
+            obstacle_fraction: 0.0,
+            base_weight: 10,
+            perturbation: 0.0,
+        }
+    }
+}
+
+impl GridOptions {
+    /// Largest `scale` for which [`crate::algorithms::EuclideanHeuristic`] stays
+    /// admissible on a 2D grid built with these options
+    pub fn heuristic_scale(&self) -> f64 {
+        self.base_weight as f64 * (1.0 - self.perturbation.clamp(0.0, 1.0))
+    }
+}
+
+/// 2D grid of `width × height` cells with undirected edges between
+/// neighbouring free cells. Cell `(x, y)` is node `y * width + x` and has
+/// position `(x, y)`.
+pub fn generate_grid_graph(width: usize, height: usize, options: &GridOptions) -> Graph {
+    generate_grid_graph_with_rng(width, height, options, &mut rand::thread_rng())
+}
+
+/// [`generate_grid_graph`] with a fixed seed
+pub fn generate_grid_graph_seeded(width: usize, height: usize, options: &GridOptions, seed: u64) -> Graph {
+    generate_grid_graph_with_rng(width, height, options, &mut StdRng::seed_from_u64(seed))
+}
+
+/// [`generate_grid_graph`] drawing from `rng`
+pub fn generate_grid_graph_with_rng<R: Rng + ?Sized>(width: usize, height: usize, options: &GridOptions, rng: &mut R) -> Graph {
+    generate_grid_3d_graph_with_rng(width, height, 1, options, rng)
+}
+
+/// 3D grid of `width × height × depth` cells. Cell `(x, y, z)` is node
+/// `(z * height + y) * width + x`. Positions are only attached when
+/// `depth == 1`, since the geometric heuristics are planar.
+pub fn generate_grid_3d_graph(width: usize, height: usize, depth: usize, options: &GridOptions) -> Graph {
+    generate_grid_3d_graph_with_rng(width, height, depth, options, &mut rand::thread_rng())
+}
+
+/// [`generate_grid_3d_graph`] with a fixed seed
+pub fn generate_grid_3d_graph_seeded(width: usize, height: usize, depth: usize, options: &GridOptions, seed: u64) -> Graph {
+    generate_grid_3d_graph_with_rng(width, height, depth, options, &mut StdRng::seed_from_u64(seed))
+}
+
+/// [`generate_grid_3d_graph`] drawing from `rng`
+pub fn generate_grid_3d_graph_with_rng<R: Rng + ?Sized>(
+    width: usize,
+    height: usize,
+    depth: usize,
+    options: &GridOptions,
+    rng: &mut R,
+) -> Graph {
+    let mut graph = Graph::new();
+    let id = |x: usize, y: usize, z: usize| (z * height + y) * width + x;
+
+    let free: Vec<bool> = (0..width * height * depth)
+        .map(|_| !rng.gen_bool(options.obstacle_fraction.clamp(0.0, 1.0)))
+        .collect();
+
+    // Offsets that point "forward", so every undirected edge is added once
+    let mut offsets = Vec::new();
+    for dz in -1i64..=1 {
+        for dy in -1i64..=1 {
+            for dx in -1i64..=1 {
+                let forward = (dz, dy, dx) > (0, 0, 0);
+                let steps = dx.abs() + dy.abs() + dz.abs();
+                let flat = depth > 1 || dz == 0;
+                if forward && flat && (steps == 1 || options.diagonals) {
+                    offsets.push((dx, dy, dz, (steps as f64).sqrt()));
+                }
+            }
+        }
+    }
+
+    for z in 0..depth {
+        for y in 0..height {
+            for x in 0..width {
+                let node = id(x, y, z);
+                if !free[node] {
+                    continue;
+                }
+
+                graph.add_node(node);
+                if depth == 1 {
+                    graph.set_position(node, x as f64, y as f64);
+                }
+
+                for &(dx, dy, dz, length) in &offsets {
+                    let (nx, ny, nz) = (x as i64 + dx, y as i64 + dy, z as i64 + dz);
+                    if nx < 0 || ny < 0 || nz < 0 || nx >= width as i64 || ny >= height as i64 || nz >= depth as i64 {
+                        continue;
+                    }
+
+                    let neighbor = id(nx as usize, ny as usize, nz as usize);
+                    if free[neighbor] {
+                        let weight = perturbed_weight(options.base_weight as f64 * length, options.perturbation, rng);
+                        graph.add_bidirectional_edge(node, neighbor, weight);
+                    }
+                }
+            }
+        }
+    }
+
+    graph
+}
+
+fn perturbed_weight<R: Rng + ?Sized>(length: f64, perturbation: f64, rng: &mut R) -> usize {
+    let perturbation = perturbation.clamp(0.0, 1.0);
+    let factor = if perturbation > 0.0 { rng.gen_range(1.0 - perturbation..=1.0 + perturbation) } else { 1.0 };
+    (length * factor).ceil() as usize
+}
+
+/// Radius at which a random geometric graph on `nodes` points in the unit
+/// square has about `degree` neighbours per node (ignoring the border)
+pub fn radius_for_average_degree(nodes: usize, degree: f64) -> f64 {
+    (degree / (nodes.max(1) as f64 * std::f64::consts::PI)).sqrt()
+}
+
+/// Random geometric graph: `nodes` points uniform in the unit square, joined
+/// by an undirected edge when closer than `radius`. Positions are the points
+/// scaled by `scale`, and each weight is the scaled Euclidean length rounded
+/// up, so [`crate::algorithms::EuclideanHeuristic`] with scale 1 is admissible.
+///
+/// Points are bucketed into at most about `n` cells of side at least
+/// `radius`, so generation takes O(n + m) expected time and O(n) space.
+pub fn generate_geometric_graph(nodes: usize, radius: f64, scale: f64) -> Graph {
+    generate_geometric_graph_with_rng(nodes, radius, scale, &mut rand::thread_rng())
+}
+
+/// [`generate_geometric_graph`] with a fixed seed
+pub fn generate_geometric_graph_seeded(nodes: usize, radius: f64, scale: f64, seed: u64) -> Graph {
+    generate_geometric_graph_with_rng(nodes, radius, scale, &mut StdRng::seed_from_u64(seed))
+}
+
+/// [`generate_geometric_graph`] drawing from `rng`
+pub fn generate_geometric_graph_with_rng<R: Rng + ?Sized>(nodes: usize, radius: f64, scale: f64, rng: &mut R) -> Graph {
+    let mut graph = Graph::new();
+    let points: Vec<(f64, f64)> = (0..nodes).map(|_| (rng.gen::<f64>(), rng.gen::<f64>())).collect();
+
+    for (node, &(x, y)) in points.iter().enumerate() {
+        graph.set_position(node, x * scale, y * scale);
+    }
+
+    if radius <= 0.0 {
+        return graph;
+    }
+
+    // Cells may be wider than `radius` (the neighbourhood test stays exact),
+    // but never more numerous than the points
+    let max_cells = (nodes as f64).sqrt().ceil() as usize;
+    let cells = ((1.0 / radius).floor() as usize).clamp(1, max_cells.max(1));
+    let cell_of = |coordinate: f64| ((coordinate * cells as f64) as usize).min(cells - 1);
+    let mut buckets: Vec<Vec<usize>> = vec![Vec::new(); cells * cells];
+    for (node, &(x, y)) in points.iter().enumerate() {
+        buckets[cell_of(y) * cells + cell_of(x)].push(node);
+    }
+
+    for (node, &(x, y)) in points.iter().enumerate() {
+        let (cx, cy) = (cell_of(x), cell_of(y));
+
+        for ny in cy.saturating_sub(1)..=(cy + 1).min(cells - 1) {
+            for nx in cx.saturating_sub(1)..=(cx + 1).min(cells - 1) {
+                for &other in &buckets[ny * cells + nx] {
+                    if other <= node {
+                        continue;
+                    }
+
+                    let (ox, oy) = points[other];
+                    let distance = ((x - ox).powi(2) + (y - oy).powi(2)).sqrt();
+                    if distance < radius {
+                        graph.add_bidirectional_edge(node, other, (distance * scale).ceil() as usize);
+                    }
+                }
+            }
+        }
+    }
+
+    graph
+}
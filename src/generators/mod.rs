@@ -0,0 +1,22 @@
+pub mod adversarial;
+pub mod gnm;
+pub mod grid;
+pub mod scale_free;
+pub mod weights;
+
+pub use adversarial::{
+    generate_chain_with_shortcuts, generate_decrease_key_graph, generate_layered_graph, generate_layered_graph_seeded,
+    generate_layered_graph_with_rng, generate_tie_grid,
+};
+pub use gnm::{edges_for_average_degree, generate_gnm_graph, generate_gnm_graph_seeded, generate_gnm_graph_with_rng};
+pub use grid::{
+    generate_geometric_graph, generate_geometric_graph_seeded, generate_geometric_graph_with_rng,
+    generate_grid_3d_graph, generate_grid_3d_graph_seeded, generate_grid_3d_graph_with_rng,
+    generate_grid_graph, generate_grid_graph_seeded, generate_grid_graph_with_rng,
+    radius_for_average_degree, GridOptions,
+};
+pub use scale_free::{
+    generate_barabasi_albert_graph, generate_barabasi_albert_graph_seeded, generate_barabasi_albert_graph_with_rng,
+    generate_rmat_graph, generate_rmat_graph_seeded, generate_rmat_graph_with_rng, RmatParams, GRAPH500_EDGE_FACTOR,
+};
+pub use weights::WeightDistribution;
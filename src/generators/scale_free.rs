@@ -0,0 +1,150 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use crate::generators::weights::WeightDistribution;
+use crate::graph::Graph;
+
+/// Barabási–Albert preferential attachment: start from a clique on
+/// `attachments + 1` nodes, then connect every new node to `attachments`
+/// distinct existing nodes picked with probability proportional to their
+/// degree. Edges are undirected and the degree distribution follows a power
+/// law with exponent 3.
+pub fn generate_barabasi_albert_graph(nodes: usize, attachments: usize, weights: &WeightDistribution) -> Graph {
+    generate_barabasi_albert_graph_with_rng(nodes, attachments, weights, &mut rand::thread_rng())
+}
+
+/// [`generate_barabasi_albert_graph`] with a fixed seed
+pub fn generate_barabasi_albert_graph_seeded(nodes: usize, attachments: usize, weights: &WeightDistribution, seed: u64) -> Graph {
+    generate_barabasi_albert_graph_with_rng(nodes, attachments, weights, &mut StdRng::seed_from_u64(seed))
+}
+
+/// [`generate_barabasi_albert_graph`] drawing from `rng`
+pub fn generate_barabasi_albert_graph_with_rng<R: Rng + ?Sized>(
+    nodes: usize,
+    attachments: usize,
+    weights: &WeightDistribution,
+    rng: &mut R,
+) -> Graph {
+    let mut graph = Graph::new();
+    for i in 0..nodes {
+        graph.add_node(i);
+    }
+
+    let attachments = attachments.max(1);
+    let initial = (attachments + 1).min(nodes);
+    // Every node appears once per incident edge, so a uniform pick from this
+    // list is a degree-proportional pick
+    let mut endpoints = Vec::new();
+
+    for i in 0..initial {
+        for j in i + 1..initial {
+            graph.add_bidirectional_edge(i, j, weights.sample(rng));
+            endpoints.extend([i, j]);
+        }
+    }
+
+    for node in initial..nodes {
+        let mut targets = Vec::with_capacity(attachments);
+        while targets.len() < attachments {
+            let target = endpoints[rng.gen_range(0..endpoints.len())];
+            if !targets.contains(&target) {
+                targets.push(target);
+            }
+        }
+
+        for target in targets {
+            graph.add_bidirectional_edge(node, target, weights.sample(rng));
+            endpoints.extend([node, target]);
+        }
+    }
+
+    graph
+}
+
+/// Quadrant probabilities of the R-MAT recursion. They are normalized, so
+/// only their ratios matter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RmatParams {
+    pub a: f64,
+    pub b: f64,
+    pub c: f64,
+    pub d: f64,
+}
+
+impl RmatParams {
+    /// The parameters of the Graph500 benchmark
+    pub const GRAPH500: RmatParams = RmatParams { a: 0.57, b: 0.19, c: 0.19, d: 0.05 };
+}
+
+impl Default for RmatParams {
+    fn default() -> Self {
+        RmatParams::GRAPH500
+    }
+}
+
+/// Graph500 uses 16 edges per node
+pub const GRAPH500_EDGE_FACTOR: usize = 16;
+
+/// R-MAT: `2^scale` nodes and `edge_factor · 2^scale` directed edges, each
+/// placed by descending `scale` times into one quadrant of the adjacency
+/// matrix. Node ids are randomly permuted, as in Graph500, so that high
+/// degree does not correlate with small ids. Self-loops are dropped;
+/// duplicate edges are kept as parallel edges.
+///
+/// Panics if `2^scale` or the edge count does not fit in a `usize`.
+pub fn generate_rmat_graph(scale: u32, edge_factor: usize, params: &RmatParams, weights: &WeightDistribution) -> Graph {
+    generate_rmat_graph_with_rng(scale, edge_factor, params, weights, &mut rand::thread_rng())
+}
+
+/// [`generate_rmat_graph`] with a fixed seed
+pub fn generate_rmat_graph_seeded(scale: u32, edge_factor: usize, params: &RmatParams, weights: &WeightDistribution, seed: u64) -> Graph {
+    generate_rmat_graph_with_rng(scale, edge_factor, params, weights, &mut StdRng::seed_from_u64(seed))
+}
+
+/// [`generate_rmat_graph`] drawing from `rng`
+pub fn generate_rmat_graph_with_rng<R: Rng + ?Sized>(
+    scale: u32,
+    edge_factor: usize,
+    params: &RmatParams,
+    weights: &WeightDistribution,
+    rng: &mut R,
+) -> Graph {
+    let nodes = 1usize.checked_shl(scale)
+        .unwrap_or_else(|| panic!("R-MAT scale {} is too large: 2^scale nodes must fit in a usize", scale));
+    let edges = edge_factor.checked_mul(nodes)
+        .unwrap_or_else(|| panic!("R-MAT edge count {} · 2^{} overflows a usize", edge_factor, scale));
+    let mut graph = Graph::new();
+    for i in 0..nodes {
+        graph.add_node(i);
+    }
+
+    let mut labels: Vec<usize> = (0..nodes).collect();
+    labels.shuffle(rng);
+
+    let total = params.a + params.b + params.c + params.d;
+    let (ab, abc) = ((params.a + params.b) / total, (params.a + params.b + params.c) / total);
+    let a = params.a / total;
+
+    for _ in 0..edges {
+        let (mut from, mut to) = (0, 0);
+        for bit in 0..scale {
+            let r: f64 = rng.gen();
+            if r < a {
+                // top-left: neither bit set
+            } else if r < ab {
+                to |= 1 << bit;
+            } else if r < abc {
+                from |= 1 << bit;
+            } else {
+                from |= 1 << bit;
+                to |= 1 << bit;
+            }
+        }
+
+        if from != to {
+            graph.add_edge(labels[from], labels[to], weights.sample(rng));
+        }
+    }
+
+    graph
+}
@@ -0,0 +1,88 @@
+use rand::Rng;
+
+/// How generators draw edge weights.
+///
+/// Real-valued distributions are rounded to the nearest integer, so small
+/// means produce many ties (and some zero weights). Wrap one in
+/// [`WeightDistribution::Scaled`] to keep more of its precision: scaling by
+/// 1000 stores weights as fixed-point numbers with three decimals.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WeightDistribution {
+    /// Uniform integers in `min..=max`
+    Uniform { min: usize, max: usize },
+    /// Every edge gets the same weight
+    Constant(usize),
+    /// Uniform real numbers in `min..max`
+    UniformFloat { min: f64, max: f64 },
+    /// Exponential with the given mean: mostly light edges, a few heavy ones
+    Exponential { mean: f64 },
+    /// `exp(N(mu, sigma²))`: heavy-tailed, spans orders of magnitude
+    LogNormal { mu: f64, sigma: f64 },
+    /// Uniform in `light` with probability `1 - heavy_fraction`, otherwise
+    /// uniform in `heavy` (both ranges inclusive)
+    Bimodal { light: (usize, usize), heavy: (usize, usize), heavy_fraction: f64 },
+    /// The inner distribution multiplied by a factor before rounding
+    Scaled(Box<WeightDistribution>, f64),
+}
+
+impl Default for WeightDistribution {
+    /// Uniform in `1..=100`, matching [`crate::generate_random_graph`]
+    fn default() -> Self {
+        WeightDistribution::Uniform { min: 1, max: 100 }
+    }
+}
+
+impl WeightDistribution {
+    /// Draw one weight
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        match *self {
+            WeightDistribution::Uniform { min, max } => rng.gen_range(min..=max.max(min)),
+            WeightDistribution::Constant(weight) => weight,
+            WeightDistribution::Bimodal { light, heavy, heavy_fraction } => {
+                let (min, max) = if rng.gen_bool(heavy_fraction.clamp(0.0, 1.0)) { heavy } else { light };
+                rng.gen_range(min..=max.max(min))
+            }
+            _ => self.sample_real(rng).round() as usize,
+        }
+    }
+
+    /// Draw one weight before rounding
+    pub fn sample_real<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let value = match self {
+            WeightDistribution::UniformFloat { min, max } => {
+                if max > min { rng.gen_range(*min..*max) } else { *min }
+            }
+            // Inverse transform; 1 - u lies in (0, 1], so the logarithm is finite
+            WeightDistribution::Exponential { mean } => -mean * (1.0 - rng.gen::<f64>()).ln(),
+            WeightDistribution::LogNormal { mu, sigma } => (mu + sigma * standard_normal(rng)).exp(),
+            WeightDistribution::Scaled(inner, factor) => inner.sample_real(rng) * factor,
+            _ => self.sample(rng) as f64,
+        };
+        value.max(0.0)
+    }
+
+    /// Expected weight before rounding, used to compare distributions at the same scale
+    pub fn mean(&self) -> f64 {
+        match self {
+            WeightDistribution::Uniform { min, max } => (*min + (*max).max(*min)) as f64 / 2.0,
+            WeightDistribution::Constant(weight) => *weight as f64,
+            WeightDistribution::UniformFloat { min, max } => (min + max.max(*min)) / 2.0,
+            WeightDistribution::Exponential { mean } => *mean,
+            WeightDistribution::LogNormal { mu, sigma } => (mu + sigma * sigma / 2.0).exp(),
+            WeightDistribution::Bimodal { light, heavy, heavy_fraction } => {
+                let light_mean = (light.0 + light.1.max(light.0)) as f64 / 2.0;
+                let heavy_mean = (heavy.0 + heavy.1.max(heavy.0)) as f64 / 2.0;
+                let p = heavy_fraction.clamp(0.0, 1.0);
+                (1.0 - p) * light_mean + p * heavy_mean
+            }
+            WeightDistribution::Scaled(inner, factor) => inner.mean() * factor,
+        }
+    }
+}
+
+// Box–Muller transform
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+    (-2.0 * u.ln()).sqrt() * (2.0 * std::f64::consts::PI * v).cos()
+}
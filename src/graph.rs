@@ -1,9 +1,16 @@
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::Path;
+use serde::{Deserialize, Serialize, Serializer};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "GraphData<Vec<(usize, usize)>>")]
 pub struct Graph {
     adjacency_list: HashMap<usize, Vec<(usize, usize)>>,
     nodes: Vec<usize>,
+    positions: HashMap<usize, (f64, f64)>,
 }
 
 impl Graph {
@@ -11,12 +18,13 @@ impl Graph {
         Graph {
             adjacency_list: HashMap::new(),
             nodes: Vec::new(),
+            positions: HashMap::new(),
         }
     }
     
     pub fn add_node(&mut self, node: usize) {
-        if !self.adjacency_list.contains_key(&node) {
-            self.adjacency_list.insert(node, Vec::new());
+        if let Entry::Vacant(entry) = self.adjacency_list.entry(node) {
+            entry.insert(Vec::new());
             self.nodes.push(node);
         }
     }
@@ -31,6 +39,28 @@ impl Graph {
             .push((to, weight));
     }
     
+    /// Replace every edge `from -> to` with a single edge of the given weight,
+    /// adding it if there was none
+    pub fn set_edge_weight(&mut self, from: usize, to: usize, weight: usize) {
+        self.add_node(from);
+        self.add_node(to);
+        
+        let edges = self.adjacency_list.get_mut(&from).unwrap();
+        edges.retain(|&(neighbor, _)| neighbor != to);
+        edges.push((to, weight));
+    }
+    
+    /// Remove every edge `from -> to`. Returns the weight of the cheapest one
+    /// removed, or `None` if there was no such edge.
+    pub fn remove_edge(&mut self, from: usize, to: usize) -> Option<usize> {
+        let removed = self.edge_weight(from, to)?;
+        self.adjacency_list
+            .get_mut(&from)
+            .unwrap()
+            .retain(|&(neighbor, _)| neighbor != to);
+        Some(removed)
+    }
+    
     pub fn add_bidirectional_edge(&mut self, node1: usize, node2: usize, weight: usize) {
         self.add_edge(node1, node2, weight);
         self.add_edge(node2, node1, weight);
@@ -42,6 +72,23 @@ impl Graph {
         self.adjacency_list.get(&node).map_or(&[], |v| v.as_slice())
     }
     
+    /// Neighbors of `node` that are not hidden by `mask`
+    pub fn neighbors_masked<'a>(&'a self, node: usize, mask: &'a GraphMask) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.neighbors(node)
+            .iter()
+            .copied()
+            .filter(move |&(to, _)| !mask.is_node_masked(to) && !mask.is_edge_masked(node, to))
+    }
+    
+    /// Weight of the cheapest edge `from -> to`, if any
+    pub fn edge_weight(&self, from: usize, to: usize) -> Option<usize> {
+        self.neighbors(from)
+            .iter()
+            .filter(|&&(neighbor, _)| neighbor == to)
+            .map(|&(_, weight)| weight)
+            .min()
+    }
+    
     pub fn nodes(&self) -> &Vec<usize> {
         &self.nodes
     }
@@ -53,4 +100,180 @@ impl Graph {
     pub fn edge_count(&self) -> usize {
         self.adjacency_list.values().map(|v| v.len()).sum()
     }
+    
+    /// Smallest and largest edge weight, `None` for a graph without edges
+    pub fn weight_range(&self) -> Option<(usize, usize)> {
+        let mut weights = self.adjacency_list.values().flatten().map(|&(_, weight)| weight);
+        let first = weights.next()?;
+        Some(weights.fold((first, first), |(min, max), w| (min.min(w), max.max(w))))
+    }
+    
+    /// Attach planar coordinates to a node (used by geometric heuristics)
+    pub fn set_position(&mut self, node: usize, x: f64, y: f64) {
+        self.add_node(node);
+        self.positions.insert(node, (x, y));
+    }
+    
+    pub fn position(&self, node: usize) -> Option<(f64, f64)> {
+        self.positions.get(&node).copied()
+    }
+    
+    /// Build the reverse graph: every edge `u -> v` becomes `v -> u` with the same weight
+    pub fn reversed(&self) -> Graph {
+        let mut reverse = Graph::new();
+        
+        for &node in &self.nodes {
+            reverse.add_node(node);
+        }
+        reverse.positions = self.positions.clone();
+        for &from in &self.nodes {
+            for &(to, weight) in self.neighbors(from) {
+                reverse.add_edge(to, from, weight);
+            }
+        }
+        
+        reverse
+    }
+    
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(self)
+    }
+    
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+    
+    /// Binary encoding (bincode): fixed-width integers and exact floats, much
+    /// faster to read back than JSON
+    pub fn to_binary(&self) -> bincode::Result<Vec<u8>> {
+        bincode::serialize(self)
+    }
+    
+    pub fn from_binary(bytes: &[u8]) -> bincode::Result<Self> {
+        bincode::deserialize(bytes)
+    }
+    
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_json()?)
+    }
+    
+    pub fn load_json<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(Self::from_json(&fs::read_to_string(path)?)?)
+    }
+    
+    pub fn save_binary<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_binary().map_err(binary_error)?)
+    }
+    
+    pub fn load_binary<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_binary(&fs::read(path)?).map_err(binary_error)
+    }
+}
+
+// Decoding works on bytes already in memory, so even bincode's I/O errors
+// (truncated input) mean the data is invalid
+fn binary_error(error: bincode::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+// Serialized form: nodes in insertion order, each with its outgoing edges, so
+// a round trip reproduces iteration order exactly (`A` is a borrowed edge list
+// when writing and an owned one when reading). Positions are sorted by node
+// to keep the output deterministic.
+#[derive(Serialize, Deserialize)]
+struct GraphData<A> {
+    nodes: Vec<usize>,
+    adjacency: Vec<A>,
+    positions: Vec<(usize, f64, f64)>,
+}
+
+impl Serialize for Graph {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut positions: Vec<(usize, f64, f64)> = self.positions.iter().map(|(&node, &(x, y))| (node, x, y)).collect();
+        positions.sort_unstable_by_key(|&(node, _, _)| node);
+        
+        GraphData {
+            nodes: self.nodes.clone(),
+            adjacency: self.nodes.iter().map(|&node| self.neighbors(node)).collect(),
+            positions,
+        }
+        .serialize(serializer)
+    }
+}
+
+// Every node an edge or position mentions is listed in `nodes`, so anything
+// else means the data was truncated or edited and must not load silently
+impl TryFrom<GraphData<Vec<(usize, usize)>>> for Graph {
+    type Error = String;
+    
+    fn try_from(data: GraphData<Vec<(usize, usize)>>) -> Result<Self, Self::Error> {
+        if data.adjacency.len() != data.nodes.len() {
+            return Err(format!("{} nodes but {} adjacency lists", data.nodes.len(), data.adjacency.len()));
+        }
+        
+        let mut graph = Graph::new();
+        for &node in &data.nodes {
+            if graph.adjacency_list.contains_key(&node) {
+                return Err(format!("node {} is listed twice", node));
+            }
+            graph.add_node(node);
+        }
+        for (&node, edges) in data.nodes.iter().zip(data.adjacency) {
+            for (to, weight) in edges {
+                if !graph.adjacency_list.contains_key(&to) {
+                    return Err(format!("edge {} -> {} leads to an unlisted node", node, to));
+                }
+                graph.add_edge(node, to, weight);
+            }
+        }
+        for (node, x, y) in data.positions {
+            if !graph.adjacency_list.contains_key(&node) {
+                return Err(format!("position given for unlisted node {}", node));
+            }
+            graph.set_position(node, x, y);
+        }
+        
+        Ok(graph)
+    }
+}
+
+impl Default for Graph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Nodes and edges hidden from a search without modifying the graph.
+/// Masking an edge hides every parallel edge between the same two nodes.
+#[derive(Debug, Clone, Default)]
+pub struct GraphMask {
+    nodes: HashSet<usize>,
+    edges: HashSet<(usize, usize)>,
+}
+
+impl GraphMask {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    pub fn mask_node(&mut self, node: usize) {
+        self.nodes.insert(node);
+    }
+    
+    pub fn mask_edge(&mut self, from: usize, to: usize) {
+        self.edges.insert((from, to));
+    }
+    
+    pub fn is_node_masked(&self, node: usize) -> bool {
+        self.nodes.contains(&node)
+    }
+    
+    pub fn is_edge_masked(&self, from: usize, to: usize) -> bool {
+        self.edges.contains(&(from, to))
+    }
+    
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.edges.clear();
+    }
 }
@@ -0,0 +1,301 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::Path;
+use memmap2::Mmap;
+use crate::graph::Graph;
+
+// Binary CSR (compressed sparse row) files. Everything is little-endian:
+//
+//   offset  size  field
+//        0     8  magic `SSSPCSR\0`
+//        8     4  format version (u32)
+//       12     4  flags, reserved (u32, zero)
+//       16     8  node count n (u64)
+//       24     8  edge count m (u64)
+//       32     4  CRC-32 of bytes 0..32 followed by the payload (u32)
+//       36     4  padding
+//       40  8·n   node ids, in `Graph::nodes` order (u64)
+//         8·n+8   edge offsets per node, starting at 0 and ending at m (u64)
+//          4·m    edge targets as node indices (u32)
+//          4·m    edge weights (u32)
+//
+// Every section starts at a multiple of its element size from the start of
+// the file, so a mapped file is read in place without copying.
+
+const MAGIC: &[u8; 8] = b"SSSPCSR\0";
+/// Version written by [`write_csr`]; files with any other version are rejected
+pub const CSR_VERSION: u32 = 1;
+const HEADER_LEN: usize = 40;
+const CHECKSUM_AT: usize = 32;
+
+/// A graph in CSR form, either memory-mapped from a file or read into memory.
+///
+/// Nodes are addressed by their index `0..node_count()` in the file; the
+/// original ids are in [`CsrGraph::node_ids`]. Positions are not stored.
+pub struct CsrGraph {
+    storage: Storage,
+    nodes: usize,
+    edges: usize,
+}
+
+enum Storage {
+    Mapped(Mmap),
+    // Words rather than bytes so the buffer is 8-byte aligned like a mapping
+    Owned { words: Vec<u64>, len: usize },
+}
+
+impl CsrGraph {
+    /// Map a file written by [`write_csr`]. The header, checksum and
+    /// structure are validated once here; the data is not copied.
+    ///
+    /// The file must not be modified while it is mapped.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only and, as documented above, the file
+        // is not modified while it is in use
+        let mmap = unsafe { Mmap::map(&file)? };
+        Self::validate(Storage::Mapped(mmap))
+    }
+
+    /// Read a whole CSR file from `reader` into memory
+    pub fn read<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+
+        let mut words = vec![0u64; bytes.len().div_ceil(8)];
+        for (word, chunk) in words.iter_mut().zip(bytes.chunks(8)) {
+            let mut buffer = [0; 8];
+            buffer[..chunk.len()].copy_from_slice(chunk);
+            *word = u64::from_ne_bytes(buffer);
+        }
+        Self::validate(Storage::Owned { words, len: bytes.len() })
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges
+    }
+
+    /// Original id of every node, by index
+    pub fn node_ids(&self) -> &[u64] {
+        cast(&self.bytes()[HEADER_LEN..HEADER_LEN + 8 * self.nodes])
+    }
+
+    /// `offsets()[i]..offsets()[i + 1]` are the edges of node `i`
+    pub fn offsets(&self) -> &[u64] {
+        let start = HEADER_LEN + 8 * self.nodes;
+        cast(&self.bytes()[start..start + 8 * (self.nodes + 1)])
+    }
+
+    pub fn targets(&self) -> &[u32] {
+        let start = HEADER_LEN + 16 * self.nodes + 8;
+        cast(&self.bytes()[start..start + 4 * self.edges])
+    }
+
+    pub fn weights(&self) -> &[u32] {
+        let start = HEADER_LEN + 16 * self.nodes + 8 + 4 * self.edges;
+        cast(&self.bytes()[start..start + 4 * self.edges])
+    }
+
+    /// Outgoing edges of node index `node` as `(target index, weight)`
+    pub fn neighbors(&self, node: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let offsets = self.offsets();
+        let range = offsets[node] as usize..offsets[node + 1] as usize;
+        self.targets()[range.clone()].iter()
+            .zip(&self.weights()[range])
+            .map(|(&to, &weight)| (to as usize, weight as usize))
+    }
+
+    /// Index of the node with the given original id, found by linear scan
+    pub fn index_of(&self, id: usize) -> Option<usize> {
+        self.node_ids().iter().position(|&node| node == id as u64)
+    }
+
+    /// Dijkstra straight on the CSR arrays, from node index `source`.
+    /// Distances are indexed like the nodes, `usize::MAX` for unreachable.
+    pub fn dijkstra(&self, source: usize) -> Vec<usize> {
+        let mut distances = vec![usize::MAX; self.nodes];
+        let mut heap = BinaryHeap::new();
+        if source >= self.nodes {
+            return distances;
+        }
+
+        distances[source] = 0;
+        heap.push(Reverse((0, source)));
+        while let Some(Reverse((cost, node))) = heap.pop() {
+            if cost > distances[node] {
+                continue;
+            }
+            for (to, weight) in self.neighbors(node) {
+                let next = cost.saturating_add(weight);
+                if next < distances[to] {
+                    distances[to] = next;
+                    heap.push(Reverse((next, to)));
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Copy into a [`Graph`] with the original node ids and node order
+    pub fn to_graph(&self) -> Graph {
+        let ids = self.node_ids();
+        let mut graph = Graph::new();
+        for &id in ids {
+            graph.add_node(id as usize);
+        }
+        for (node, &id) in ids.iter().enumerate() {
+            for (to, weight) in self.neighbors(node) {
+                graph.add_edge(id as usize, ids[to] as usize, weight);
+            }
+        }
+        graph
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match &self.storage {
+            Storage::Mapped(mmap) => mmap,
+            Storage::Owned { words, len } => &cast_bytes(words)[..*len],
+        }
+    }
+
+    fn validate(storage: Storage) -> io::Result<Self> {
+        if cfg!(target_endian = "big") {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "CSR files can only be mapped on little-endian targets"));
+        }
+
+        let mut csr = CsrGraph { storage, nodes: 0, edges: 0 };
+        let bytes = csr.bytes();
+        if bytes.len() < HEADER_LEN || &bytes[..8] != MAGIC {
+            return Err(invalid("not a CSR graph file"));
+        }
+        let version = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        if version != CSR_VERSION {
+            return Err(invalid(format!("unsupported CSR version {} (expected {})", version, CSR_VERSION)));
+        }
+
+        let nodes = u64::from_le_bytes(bytes[16..24].try_into().unwrap());
+        let edges = u64::from_le_bytes(bytes[24..32].try_into().unwrap());
+        let expected = payload_len(nodes, edges).and_then(|len| len.checked_add(HEADER_LEN as u64));
+        if expected != Some(bytes.len() as u64) {
+            return Err(invalid(format!("file is {} bytes, header describes {} nodes and {} edges", bytes.len(), nodes, edges)));
+        }
+
+        let stored = u32::from_le_bytes(bytes[CHECKSUM_AT..CHECKSUM_AT + 4].try_into().unwrap());
+        let mut hasher = crc32fast::Hasher::new();
+        hasher.update(&bytes[..CHECKSUM_AT]);
+        hasher.update(&bytes[HEADER_LEN..]);
+        if hasher.finalize() != stored {
+            return Err(invalid("checksum mismatch, the file is corrupt"));
+        }
+
+        // The length check above guarantees both fit in memory
+        csr.nodes = nodes as usize;
+        csr.edges = edges as usize;
+
+        let offsets = csr.offsets();
+        if offsets[0] != 0 || offsets[csr.nodes] != edges || offsets.windows(2).any(|pair| pair[0] > pair[1]) {
+            return Err(invalid("edge offsets are not a non-decreasing sequence from 0 to the edge count"));
+        }
+        if csr.targets().iter().any(|&to| to as u64 >= nodes) {
+            return Err(invalid("edge target out of range"));
+        }
+
+        Ok(csr)
+    }
+}
+
+/// Write `graph` as a CSR file. Fails with [`io::ErrorKind::InvalidInput`] if
+/// it has 2³² nodes or more, or a weight that does not fit in 32 bits.
+pub fn write_csr<W: Write>(graph: &Graph, writer: W) -> io::Result<()> {
+    let payload = payload(graph)?;
+    let nodes = graph.node_count() as u64;
+    let edges = (payload.len() as u64 - 16 * nodes - 8) / 8;
+
+    let mut header = [0u8; HEADER_LEN];
+    header[..8].copy_from_slice(MAGIC);
+    header[8..12].copy_from_slice(&CSR_VERSION.to_le_bytes());
+    header[16..24].copy_from_slice(&nodes.to_le_bytes());
+    header[24..32].copy_from_slice(&edges.to_le_bytes());
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(&header[..CHECKSUM_AT]);
+    hasher.update(&payload);
+    header[CHECKSUM_AT..CHECKSUM_AT + 4].copy_from_slice(&hasher.finalize().to_le_bytes());
+
+    let mut writer = BufWriter::new(writer);
+    writer.write_all(&header)?;
+    writer.write_all(&payload)?;
+    writer.flush()
+}
+
+pub fn save_csr<P: AsRef<Path>>(graph: &Graph, path: P) -> io::Result<()> {
+    write_csr(graph, File::create(path)?)
+}
+
+/// Map a CSR file and copy it into a [`Graph`]
+pub fn load_csr<P: AsRef<Path>>(path: P) -> io::Result<Graph> {
+    Ok(CsrGraph::open(path)?.to_graph())
+}
+
+// Node ids, offsets, targets and weights, laid out as in the file. Building
+// it in memory lets the checksum be computed before anything is written.
+fn payload(graph: &Graph) -> io::Result<Vec<u8>> {
+    let nodes = graph.nodes();
+    if nodes.len() > u32::MAX as usize {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "too many nodes for 32-bit edge targets"));
+    }
+    let index: HashMap<usize, u32> = nodes.iter().enumerate().map(|(i, &node)| (node, i as u32)).collect();
+
+    let mut offsets = Vec::with_capacity(8 * (nodes.len() + 1));
+    let mut targets = Vec::new();
+    let mut weights = Vec::new();
+    offsets.extend_from_slice(&0u64.to_le_bytes());
+    for &node in nodes {
+        for &(to, weight) in graph.neighbors(node) {
+            let weight = u32::try_from(weight)
+                .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("weight {} does not fit in 32 bits", weight)))?;
+            targets.extend_from_slice(&index[&to].to_le_bytes());
+            weights.extend_from_slice(&weight.to_le_bytes());
+        }
+        offsets.extend_from_slice(&(targets.len() as u64 / 4).to_le_bytes());
+    }
+
+    let mut payload = Vec::with_capacity(8 * nodes.len() + offsets.len() + 2 * targets.len());
+    for &node in nodes {
+        payload.extend_from_slice(&(node as u64).to_le_bytes());
+    }
+    payload.extend_from_slice(&offsets);
+    payload.extend_from_slice(&targets);
+    payload.extend_from_slice(&weights);
+    Ok(payload)
+}
+
+fn payload_len(nodes: u64, edges: u64) -> Option<u64> {
+    let node_bytes = nodes.checked_mul(16)?.checked_add(8)?;
+    node_bytes.checked_add(edges.checked_mul(8)?)
+}
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+// Reinterpret validated, aligned bytes as integers; the file is little-endian
+// and `validate` refuses big-endian targets
+fn cast<T: Copy>(bytes: &[u8]) -> &[T] {
+    let size = std::mem::size_of::<T>();
+    assert!(bytes.len().is_multiple_of(size) && (bytes.as_ptr() as usize).is_multiple_of(std::mem::align_of::<T>()));
+    // SAFETY: length and alignment are checked above, and T is only ever u32 or
+    // u64, for which every bit pattern is valid
+    unsafe { std::slice::from_raw_parts(bytes.as_ptr().cast(), bytes.len() / size) }
+}
+
+fn cast_bytes(words: &[u64]) -> &[u8] {
+    // SAFETY: u8 has no alignment requirement and the length covers exactly the words
+    unsafe { std::slice::from_raw_parts(words.as_ptr().cast(), words.len() * 8) }
+}
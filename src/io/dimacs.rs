@@ -0,0 +1,203 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+use crate::graph::Graph;
+use crate::io::ParseError;
+
+// Readers and writers for the 9th DIMACS Implementation Challenge formats:
+// `.gr` graphs (`p sp n m`, `a u v w`), `.ss` source lists (`p aux sp ss k`,
+// `s u`) and `.p2p` query pairs (`p aux sp p2p k`, `q s t`). Lines starting
+// with `c` are comments. DIMACS ids are 1-based; ids in this crate are 0-based.
+
+/// Read a `.gr` graph. Node `i` of the file becomes node `i - 1`, and all
+/// `n` nodes are added even if they have no arcs. Malformed input yields an
+/// [`io::ErrorKind::InvalidData`] error wrapping a [`ParseError`].
+pub fn read_gr<R: BufRead>(reader: R) -> io::Result<Graph> {
+    let mut graph = Graph::new();
+    let mut header: Option<(usize, usize)> = None;
+    let mut arcs = 0;
+    let mut lines_read = 0;
+
+    for (index, text) in reader.lines().enumerate() {
+        let text = text?;
+        let line = index + 1;
+        lines_read = line;
+        let mut fields = text.split_whitespace();
+
+        match (fields.next(), header) {
+            (None | Some("c"), _) => {}
+            (Some("p"), None) => {
+                keyword(fields.next(), "sp", line)?;
+                let nodes = number(fields.next(), line, "node count")?;
+                let edges = number(fields.next(), line, "arc count")?;
+                end(fields, line)?;
+
+                for node in 0..nodes {
+                    graph.add_node(node);
+                }
+                header = Some((nodes, edges));
+            }
+            (Some("p"), Some(_)) => return Err(ParseError::new(line, "duplicate problem line").into()),
+            (Some("a"), Some((nodes, _))) => {
+                let from = node(fields.next(), nodes, line)?;
+                let to = node(fields.next(), nodes, line)?;
+                let weight = number(fields.next(), line, "arc weight")?;
+                end(fields, line)?;
+
+                graph.add_edge(from, to, weight);
+                arcs += 1;
+            }
+            (Some("a"), None) => return Err(ParseError::new(line, "arc before the problem line").into()),
+            (Some(other), _) => return Err(ParseError::new(line, format!("unknown line type `{}`", other)).into()),
+        }
+    }
+
+    match header {
+        None => Err(ParseError::new(lines_read, "missing problem line `p sp <nodes> <arcs>`").into()),
+        Some((_, expected)) if arcs != expected => {
+            Err(ParseError::new(lines_read, format!("expected {} arcs, found {}", expected, arcs)).into())
+        }
+        Some(_) => Ok(graph),
+    }
+}
+
+/// Read a `.ss` file: the 0-based sources of single-source queries
+pub fn read_ss<R: BufRead>(reader: R) -> io::Result<Vec<usize>> {
+    read_queries(reader, "ss", "s", |fields, line| node(fields.next(), usize::MAX, line))
+}
+
+/// Read a `.p2p` file: 0-based `(source, target)` pairs
+pub fn read_p2p<R: BufRead>(reader: R) -> io::Result<Vec<(usize, usize)>> {
+    read_queries(reader, "p2p", "q", |fields, line| {
+        Ok((node(fields.next(), usize::MAX, line)?, node(fields.next(), usize::MAX, line)?))
+    })
+}
+
+fn read_queries<R, T, F>(reader: R, kind: &str, tag: &str, mut parse: F) -> io::Result<Vec<T>>
+where
+    R: BufRead,
+    F: FnMut(&mut std::str::SplitWhitespace<'_>, usize) -> Result<T, ParseError>,
+{
+    let mut queries = Vec::new();
+    let mut expected: Option<usize> = None;
+    let mut lines_read = 0;
+
+    for (index, text) in reader.lines().enumerate() {
+        let text = text?;
+        let line = index + 1;
+        lines_read = line;
+        let mut fields = text.split_whitespace();
+
+        match fields.next() {
+            None | Some("c") => {}
+            Some("p") if expected.is_some() => return Err(ParseError::new(line, "duplicate problem line").into()),
+            Some("p") => {
+                keyword(fields.next(), "aux", line)?;
+                keyword(fields.next(), "sp", line)?;
+                keyword(fields.next(), kind, line)?;
+                expected = Some(number(fields.next(), line, "query count")?);
+                end(fields, line)?;
+            }
+            Some(first) if first == tag => {
+                if expected.is_none() {
+                    return Err(ParseError::new(line, "query before the problem line").into());
+                }
+                queries.push(parse(&mut fields, line)?);
+                end(fields, line)?;
+            }
+            Some(other) => return Err(ParseError::new(line, format!("unknown line type `{}`", other)).into()),
+        }
+    }
+
+    match expected {
+        None => Err(ParseError::new(lines_read, format!("missing problem line `p aux sp {} <count>`", kind)).into()),
+        Some(count) if count != queries.len() => {
+            Err(ParseError::new(lines_read, format!("expected {} queries, found {}", count, queries.len())).into())
+        }
+        Some(_) => Ok(queries),
+    }
+}
+
+/// Write `graph` as a `.gr` file. Node `i` is written as `i + 1`, so `n` is
+/// the largest id plus one and missing ids become isolated nodes.
+pub fn write_gr<W: Write>(graph: &Graph, mut writer: W) -> io::Result<()> {
+    let mut nodes = graph.nodes().clone();
+    nodes.sort_unstable();
+    let count = nodes.last().map_or(0, |&max| max + 1);
+
+    writeln!(writer, "p sp {} {}", count, graph.edge_count())?;
+    for node in nodes {
+        for &(to, weight) in graph.neighbors(node) {
+            writeln!(writer, "a {} {} {}", node + 1, to + 1, weight)?;
+        }
+    }
+
+    writer.flush()
+}
+
+/// Write 0-based sources as a `.ss` file
+pub fn write_ss<W: Write>(sources: &[usize], mut writer: W) -> io::Result<()> {
+    writeln!(writer, "p aux sp ss {}", sources.len())?;
+    for &source in sources {
+        writeln!(writer, "s {}", source + 1)?;
+    }
+    writer.flush()
+}
+
+/// Write 0-based `(source, target)` pairs as a `.p2p` file
+pub fn write_p2p<W: Write>(pairs: &[(usize, usize)], mut writer: W) -> io::Result<()> {
+    writeln!(writer, "p aux sp p2p {}", pairs.len())?;
+    for &(source, target) in pairs {
+        writeln!(writer, "q {} {}", source + 1, target + 1)?;
+    }
+    writer.flush()
+}
+
+pub fn load_gr<P: AsRef<Path>>(path: P) -> io::Result<Graph> {
+    read_gr(BufReader::new(File::open(path)?))
+}
+
+pub fn load_ss<P: AsRef<Path>>(path: P) -> io::Result<Vec<usize>> {
+    read_ss(BufReader::new(File::open(path)?))
+}
+
+pub fn load_p2p<P: AsRef<Path>>(path: P) -> io::Result<Vec<(usize, usize)>> {
+    read_p2p(BufReader::new(File::open(path)?))
+}
+
+pub fn save_gr<P: AsRef<Path>>(graph: &Graph, path: P) -> io::Result<()> {
+    write_gr(graph, BufWriter::new(File::create(path)?))
+}
+
+fn number<T: FromStr>(field: Option<&str>, line: usize, what: &str) -> Result<T, ParseError> {
+    let field = field.ok_or_else(|| ParseError::new(line, format!("missing {}", what)))?;
+    field.parse().map_err(|_| ParseError::new(line, format!("invalid {} `{}`", what, field)))
+}
+
+// A 1-based id in `1..=nodes`, returned 0-based
+fn node(field: Option<&str>, nodes: usize, line: usize) -> Result<usize, ParseError> {
+    let id: usize = number(field, line, "node id")?;
+    if id == 0 {
+        return Err(ParseError::new(line, "node ids are 1-based, found 0"));
+    }
+    if id > nodes {
+        return Err(ParseError::new(line, format!("node id {} out of range 1..={}", id, nodes)));
+    }
+    Ok(id - 1)
+}
+
+fn keyword(field: Option<&str>, expected: &str, line: usize) -> Result<(), ParseError> {
+    match field {
+        Some(found) if found == expected => Ok(()),
+        Some(found) => Err(ParseError::new(line, format!("expected `{}`, found `{}`", expected, found))),
+        None => Err(ParseError::new(line, format!("expected `{}`", expected))),
+    }
+}
+
+fn end<'a>(mut fields: impl Iterator<Item = &'a str>, line: usize) -> Result<(), ParseError> {
+    match fields.next() {
+        Some(extra) => Err(ParseError::new(line, format!("unexpected field `{}`", extra))),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::graph::Graph;
+use crate::io::ParseError;
+
+/// How to read a plain edge list such as the SNAP datasets
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeListOptions {
+    /// Field separator; `None` splits on runs of whitespace
+    pub delimiter: Option<char>,
+    /// Lines starting with this (after leading whitespace) are skipped
+    pub comment_prefix: String,
+    pub source_column: usize,
+    pub target_column: usize,
+    /// Column holding the weight; `None` gives every edge `default_weight`
+    pub weight_column: Option<usize>,
+    pub default_weight: usize,
+    /// Weights are multiplied by this and rounded, so fractional weights can
+    /// be kept as fixed-point integers
+    pub weight_scale: f64,
+    /// Undirected lists get every edge in both directions
+    pub directed: bool,
+    /// Give labels dense ids `0..n` in order of first appearance. Without it,
+    /// labels must be non-negative integers and are used as ids directly.
+    pub remap: bool,
+}
+
+impl Default for EdgeListOptions {
+    /// Whitespace-separated, `#` comments, unweighted (weight 1), directed, remapped
+    fn default() -> Self {
+        EdgeListOptions {
+            delimiter: None,
+            comment_prefix: "#".to_string(),
+            source_column: 0,
+            target_column: 1,
+            weight_column: None,
+            default_weight: 1,
+            weight_scale: 1.0,
+            directed: true,
+            remap: true,
+        }
+    }
+}
+
+/// Correspondence between the labels in an input file and node ids
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdMap {
+    ids: HashMap<String, usize>,
+    labels: HashMap<usize, String>,
+}
+
+impl IdMap {
+    pub fn id(&self, label: &str) -> Option<usize> {
+        self.ids.get(label).copied()
+    }
+
+    pub fn label(&self, id: usize) -> Option<&str> {
+        self.labels.get(&id).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    fn insert(&mut self, label: &str, id: usize) {
+        self.ids.insert(label.to_string(), id);
+        self.labels.entry(id).or_insert_with(|| label.to_string());
+    }
+}
+
+/// Read an edge list, returning the graph and the label of every node.
+/// Malformed lines yield an [`io::ErrorKind::InvalidData`] error wrapping a
+/// [`ParseError`].
+pub fn read_edge_list<R: BufRead>(reader: R, options: &EdgeListOptions) -> io::Result<(Graph, IdMap)> {
+    let mut graph = Graph::new();
+    let mut map = IdMap::default();
+
+    for (index, text) in reader.lines().enumerate() {
+        let text = text?;
+        let line = index + 1;
+        let trimmed = text.trim();
+        if trimmed.is_empty() || (!options.comment_prefix.is_empty() && trimmed.starts_with(&options.comment_prefix)) {
+            continue;
+        }
+
+        let fields: Vec<&str> = match options.delimiter {
+            Some(delimiter) => trimmed.split(delimiter).map(str::trim).collect(),
+            None => trimmed.split_whitespace().collect(),
+        };
+        let column = |column: usize, what: &str| -> Result<&str, ParseError> {
+            fields.get(column).copied().filter(|field| !field.is_empty())
+                .ok_or_else(|| ParseError::new(line, format!("missing {} in column {}", what, column + 1)))
+        };
+
+        let from = node_id(column(options.source_column, "source")?, &mut map, options.remap, line)?;
+        let to = node_id(column(options.target_column, "target")?, &mut map, options.remap, line)?;
+        let weight = match options.weight_column {
+            Some(weight_column) => parse_weight(column(weight_column, "weight")?, options.weight_scale, line)?,
+            None => options.default_weight,
+        };
+
+        if options.directed {
+            graph.add_edge(from, to, weight);
+        } else {
+            graph.add_bidirectional_edge(from, to, weight);
+        }
+    }
+
+    Ok((graph, map))
+}
+
+pub fn load_edge_list<P: AsRef<Path>>(path: P, options: &EdgeListOptions) -> io::Result<(Graph, IdMap)> {
+    read_edge_list(BufReader::new(File::open(path)?), options)
+}
+
+fn node_id(label: &str, map: &mut IdMap, remap: bool, line: usize) -> Result<usize, ParseError> {
+    if let Some(id) = map.id(label) {
+        return Ok(id);
+    }
+
+    let id = if remap {
+        map.len()
+    } else {
+        label.parse().map_err(|_| ParseError::new(line, format!("invalid node id `{}`", label)))?
+    };
+    map.insert(label, id);
+    Ok(id)
+}
+
+fn parse_weight(field: &str, scale: f64, line: usize) -> Result<usize, ParseError> {
+    if scale == 1.0 {
+        if let Ok(weight) = field.parse() {
+            return Ok(weight);
+        }
+    }
+
+    let weight = match field.parse::<f64>() {
+        Ok(weight) if weight.is_finite() && weight >= 0.0 => (weight * scale).round(),
+        _ => return Err(ParseError::new(line, format!("invalid weight `{}`", field))),
+    };
+    // `usize::MAX as f64` rounds up to 2^64, which itself does not fit
+    if !weight.is_finite() || weight < 0.0 || weight >= usize::MAX as f64 {
+        return Err(ParseError::new(line, format!("weight `{}` out of range", field)));
+    }
+    Ok(weight as usize)
+}
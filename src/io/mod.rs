@@ -0,0 +1,45 @@
+pub mod csr;
+pub mod dimacs;
+pub mod edge_list;
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+pub use csr::{load_csr, save_csr, write_csr, CsrGraph, CSR_VERSION};
+pub use dimacs::{load_gr, load_p2p, load_ss, read_gr, read_p2p, read_ss, save_gr, write_gr, write_p2p, write_ss};
+pub use edge_list::{load_edge_list, read_edge_list, EdgeListOptions, IdMap};
+
+/// A malformed line in a graph or query file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        ParseError { line, message: message.into() }
+    }
+
+    /// The parse error inside an error returned by one of the readers, if
+    /// reading failed because of the input rather than the I/O itself
+    pub fn from_io(error: &io::Error) -> Option<&ParseError> {
+        error.get_ref()?.downcast_ref()
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+impl From<ParseError> for io::Error {
+    fn from(error: ParseError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
@@ -1,7 +1,9 @@
 pub mod algorithms;
+pub mod generators;
 pub mod graph;
+pub mod io;
 pub mod utils;
 
-pub use algorithms::{dijkstra, bfs};
-pub use graph::Graph;
-pub use utils::{generate_random_graph,create_test_graph, BenchmarkResult};
+pub use algorithms::{dijkstra, bfs, bidirectional_dijkstra, bidirectional_dijkstra_with_reverse, astar};
+pub use graph::{Graph, GraphMask};
+pub use utils::{generate_random_graph, generate_random_graph_seeded, generate_random_graph_with_rng, generate_zero_weight_graph, generate_zero_weight_graph_seeded, generate_zero_weight_graph_with_rng, generate_weighted_random_graph, generate_weighted_random_graph_seeded, generate_weighted_random_graph_with_rng, seed_from_env, SEED_ENV_VAR, create_test_graph, create_zero_weight_graph, BenchmarkResult};
@@ -1,5 +1,7 @@
+use crate::generators::WeightDistribution;
 use crate::graph::Graph;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -11,10 +13,77 @@ pub struct BenchmarkResult {
     pub distances_computed: usize,
 }
 
-/// Generate a random connected graph for testing
+/// Environment variable holding the seed for reproducible benchmarks and tests
+pub const SEED_ENV_VAR: &str = "SSSP_SEED";
+
+/// Seed from [`SEED_ENV_VAR`] if set, otherwise a fresh random one. Callers
+/// should print it so a run can be replayed.
+pub fn seed_from_env() -> u64 {
+    std::env::var(SEED_ENV_VAR).ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or_else(rand::random)
+}
+
+/// Generate a random strongly connected graph for testing: a bidirectional
+/// spanning tree plus random one-way edges
 pub fn generate_random_graph(nodes: usize, edge_density: f64) -> Graph {
+    generate_random_graph_with_rng(nodes, edge_density, &mut rand::thread_rng())
+}
+
+/// [`generate_random_graph`] with a fixed seed; the same seed gives the same graph
+pub fn generate_random_graph_seeded(nodes: usize, edge_density: f64, seed: u64) -> Graph {
+    generate_random_graph_with_rng(nodes, edge_density, &mut StdRng::seed_from_u64(seed))
+}
+
+/// [`generate_random_graph`] drawing from `rng`
+pub fn generate_random_graph_with_rng<R: Rng + ?Sized>(nodes: usize, edge_density: f64, rng: &mut R) -> Graph {
+    random_graph_with_weights(nodes, edge_density, rng, |rng| rng.gen_range(1..=100))
+}
+
+/// [`generate_random_graph`] with weights drawn from `weights`
+pub fn generate_weighted_random_graph(nodes: usize, edge_density: f64, weights: &WeightDistribution) -> Graph {
+    generate_weighted_random_graph_with_rng(nodes, edge_density, weights, &mut rand::thread_rng())
+}
+
+/// [`generate_weighted_random_graph`] with a fixed seed
+pub fn generate_weighted_random_graph_seeded(nodes: usize, edge_density: f64, weights: &WeightDistribution, seed: u64) -> Graph {
+    generate_weighted_random_graph_with_rng(nodes, edge_density, weights, &mut StdRng::seed_from_u64(seed))
+}
+
+/// [`generate_weighted_random_graph`] drawing from `rng`
+pub fn generate_weighted_random_graph_with_rng<R: Rng + ?Sized>(
+    nodes: usize,
+    edge_density: f64,
+    weights: &WeightDistribution,
+    rng: &mut R,
+) -> Graph {
+    random_graph_with_weights(nodes, edge_density, rng, |rng| weights.sample(rng))
+}
+
+/// Like [`generate_random_graph`], but each edge weighs 0 with probability
+/// `zero_fraction` (otherwise 1..=100). High fractions produce zero-weight cycles.
+pub fn generate_zero_weight_graph(nodes: usize, edge_density: f64, zero_fraction: f64) -> Graph {
+    generate_zero_weight_graph_with_rng(nodes, edge_density, zero_fraction, &mut rand::thread_rng())
+}
+
+/// [`generate_zero_weight_graph`] with a fixed seed
+pub fn generate_zero_weight_graph_seeded(nodes: usize, edge_density: f64, zero_fraction: f64, seed: u64) -> Graph {
+    generate_zero_weight_graph_with_rng(nodes, edge_density, zero_fraction, &mut StdRng::seed_from_u64(seed))
+}
+
+/// [`generate_zero_weight_graph`] drawing from `rng`
+pub fn generate_zero_weight_graph_with_rng<R: Rng + ?Sized>(nodes: usize, edge_density: f64, zero_fraction: f64, rng: &mut R) -> Graph {
+    random_graph_with_weights(nodes, edge_density, rng, |rng| {
+        if rng.gen_bool(zero_fraction) { 0 } else { rng.gen_range(1..=100) }
+    })
+}
+
+fn random_graph_with_weights<R, F>(nodes: usize, edge_density: f64, rng: &mut R, mut weight: F) -> Graph
+where
+    R: Rng + ?Sized,
+    F: FnMut(&mut R) -> usize,
+{
     let mut graph = Graph::new();
-    let mut rng = rand::thread_rng();
     
     // Add all nodes
     for i in 0..nodes {
@@ -24,21 +93,21 @@ pub fn generate_random_graph(nodes: usize, edge_density: f64) -> Graph {
     // Ensure the graph is connected by creating a spanning tree
     for i in 1..nodes {
         let parent = rng.gen_range(0..i);
-        let weight = rng.gen_range(1..=100);
+        let weight = weight(rng);
         graph.add_bidirectional_edge(parent, i, weight);
     }
     
     // Add additional edges based on density
-    let max_edges = nodes * (nodes - 1) / 2;
+    let max_edges = nodes * nodes.saturating_sub(1) / 2;
     let target_edges = (max_edges as f64 * edge_density) as usize;
-    let current_edges = nodes - 1; // edges from spanning tree
+    let current_edges = nodes.saturating_sub(1); // edges from spanning tree
     
     for _ in 0..(target_edges.saturating_sub(current_edges)) {
         let from = rng.gen_range(0..nodes);
         let to = rng.gen_range(0..nodes);
         
         if from != to {
-            let weight = rng.gen_range(1..=100);
+            let weight = weight(rng);
             graph.add_edge(from, to, weight);
         }
     }
@@ -66,3 +135,26 @@ pub fn create_test_graph() -> Graph {
     
     graph
 }
+
+/// Create a small graph with zero-weight edges and zero-weight cycles
+pub fn create_zero_weight_graph() -> Graph {
+    let mut graph = Graph::new();
+    
+    // 0 -> 1 <-> 2 <-> 3 form a zero-weight cycle reached at cost 5,
+    // 3 -> 4 is free and 0 -> 4 is a more expensive direct edge;
+    // 4 -> 5 -> 6 -> 4 is a second zero-weight cycle.
+    
+    graph.add_edge(0, 1, 5);
+    graph.add_edge(1, 2, 0);
+    graph.add_edge(2, 1, 0);
+    graph.add_edge(2, 3, 0);
+    graph.add_edge(3, 2, 0);
+    graph.add_edge(3, 4, 0);
+    graph.add_edge(0, 4, 7);
+    graph.add_edge(4, 5, 0);
+    graph.add_edge(5, 6, 0);
+    graph.add_edge(6, 4, 0);
+    graph.add_edge(6, 7, 2);
+    
+    graph
+}
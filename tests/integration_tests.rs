@@ -1,4 +1,14 @@
-use sssp_algos::{dijkstra, bfs, create_test_graph, generate_random_graph};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use sssp_algos::{dijkstra, bfs, astar, bidirectional_dijkstra, bidirectional_dijkstra_with_reverse, create_test_graph, generate_random_graph_with_rng, generate_zero_weight_graph_with_rng, seed_from_env, SEED_ENV_VAR, create_zero_weight_graph, Graph};
+
+// Random graphs come from SSSP_SEED if set, otherwise from a fresh seed that is
+// printed (and shown by the test harness on failure) so the run can be replayed
+fn test_rng() -> StdRng {
+    let seed = seed_from_env();
+    println!("{}={}", SEED_ENV_VAR, seed);
+    StdRng::seed_from_u64(seed)
+}
 
 #[test]
 fn test_algorithms_consistency() {
@@ -42,7 +52,8 @@ fn test_dijkstra_correctness() {
 
 #[test]
 fn test_random_graph_generation() {
-    let graph = generate_random_graph(10, 0.3);
+    let mut rng = test_rng();
+    let graph = generate_random_graph_with_rng(10, 0.3, &mut rng);
     
     assert_eq!(graph.node_count(), 10);
     assert!(graph.edge_count() > 0);
@@ -54,3 +65,1420 @@ fn test_random_graph_generation() {
     assert_eq!(dijkstra_result.len(), 10);
     assert_eq!(new_algo_result.len(), 10);
 }
+
+#[test]
+fn test_bidirectional_dijkstra_matches_dijkstra() {
+    let mut rng = test_rng();
+    let graph = generate_random_graph_with_rng(60, 0.05, &mut rng);
+    let reverse = graph.reversed();
+    
+    for &source in graph.nodes().iter().take(5) {
+        let expected = dijkstra(&graph, source);
+        
+        for &target in graph.nodes() {
+            let result = bidirectional_dijkstra_with_reverse(&graph, &reverse, source, target);
+            
+            match result {
+                Some((distance, path)) => {
+                    assert_eq!(distance, expected[&target], "Distance mismatch for {} -> {}", source, target);
+                    assert_eq!(path.first(), Some(&source));
+                    assert_eq!(path.last(), Some(&target));
+                    
+                    // The returned path must actually have the reported cost
+                    let cost: usize = path.windows(2)
+                        .map(|pair| graph.neighbors(pair[0]).iter()
+                            .filter(|&&(to, _)| to == pair[1])
+                            .map(|&(_, weight)| weight)
+                            .min()
+                            .expect("path uses a missing edge"))
+                        .sum();
+                    assert_eq!(cost, distance);
+                }
+                None => assert_eq!(expected[&target], usize::MAX),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bidirectional_dijkstra_unreachable() {
+    let graph = create_test_graph();
+    
+    assert_eq!(bidirectional_dijkstra(&graph, 0, 6), Some((6, vec![0, 4, 5, 6])));
+    assert_eq!(bidirectional_dijkstra(&graph, 6, 0), None);
+    assert_eq!(bidirectional_dijkstra(&graph, 3, 3), Some((0, vec![3])));
+}
+
+fn create_grid_graph(width: usize, height: usize) -> Graph {
+    let mut graph = Graph::new();
+    
+    // Unit-spaced grid with weights in 10..=19 and node ids row by row
+    for y in 0..height {
+        for x in 0..width {
+            let node = y * width + x;
+            graph.set_position(node, x as f64, y as f64);
+            if x + 1 < width {
+                graph.add_bidirectional_edge(node, node + 1, 10 + (node * 7) % 10);
+            }
+            if y + 1 < height {
+                graph.add_bidirectional_edge(node, node + width, 10 + (node * 3) % 10);
+            }
+        }
+    }
+    
+    graph
+}
+
+#[test]
+fn test_astar_matches_dijkstra() {
+    use sssp_algos::algorithms::{EuclideanHeuristic, ManhattanHeuristic};
+    
+    let graph = create_grid_graph(8, 6);
+    let expected = dijkstra(&graph, 0);
+    
+    for &target in graph.nodes() {
+        let euclidean = EuclideanHeuristic::new(&graph, target, 10.0).unwrap();
+        let manhattan = ManhattanHeuristic::new(&graph, target, 10.0).unwrap();
+        
+        assert_eq!(astar(&graph, 0, target, &euclidean).map(|(d, _)| d), Some(expected[&target]));
+        assert_eq!(astar(&graph, 0, target, &manhattan).map(|(d, _)| d), Some(expected[&target]));
+        assert_eq!(astar(&graph, 0, target, &|_| 0).map(|(d, _)| d), Some(expected[&target]));
+    }
+    
+    let (_, path) = astar(&graph, 0, 47, &|_| 0).unwrap();
+    assert_eq!(path.first(), Some(&0));
+    assert_eq!(path.last(), Some(&47));
+    assert_eq!(astar(&create_test_graph(), 6, 0, &|_| 0), None);
+}
+
+#[test]
+fn test_check_heuristic() {
+    use sssp_algos::algorithms::{check_heuristic, Heuristic, ManhattanHeuristic};
+    
+    let graph = create_grid_graph(5, 5);
+    let manhattan = ManhattanHeuristic::new(&graph, 24, 10.0).unwrap();
+    let report = check_heuristic(&graph, 24, &manhattan);
+    assert!(report.is_admissible());
+    assert!(report.is_consistent());
+    
+    // Overestimating by a factor of 3 breaks admissibility
+    let inflated = ManhattanHeuristic::new(&graph, 24, 30.0).unwrap();
+    let boxed: Box<dyn Heuristic> = Box::new(inflated);
+    let report = check_heuristic(&graph, 24, boxed.as_ref());
+    assert!(!report.is_admissible());
+    assert!(report.inadmissible.iter().all(|&(_, estimate, distance)| estimate > distance));
+}
+
+#[test]
+fn test_alt_matches_dijkstra() {
+    use sssp_algos::algorithms::{alt_query, LandmarkStrategy, Landmarks};
+    
+    let mut rng = test_rng();
+    let graph = generate_random_graph_with_rng(80, 0.05, &mut rng);
+    let expected = dijkstra(&graph, 3);
+    
+    for strategy in [LandmarkStrategy::Random, LandmarkStrategy::Farthest, LandmarkStrategy::Avoid] {
+        let landmarks = Landmarks::select_with_rng(&graph, 4, strategy, &mut rng);
+        assert_eq!(landmarks.landmarks().len(), 4);
+        
+        // Preprocessing is reproducible from a seed
+        let seeded = Landmarks::select_seeded(&graph, 4, strategy, 7);
+        assert_eq!(seeded.landmarks(), Landmarks::select_seeded(&graph, 4, strategy, 7).landmarks());
+        
+        for &target in graph.nodes() {
+            assert!(landmarks.lower_bound(3, target) <= expected[&target]);
+            let result = alt_query(&graph, &landmarks, 3, target);
+            assert_eq!(result.map(|(d, _)| d), Some(expected[&target]), "{:?} to {}", strategy, target);
+        }
+    }
+}
+
+#[test]
+fn test_alt_preprocessing_round_trip() {
+    use sssp_algos::algorithms::{alt_query, LandmarkStrategy, Landmarks};
+    
+    let mut rng = test_rng();
+    let graph = generate_random_graph_with_rng(40, 0.1, &mut rng);
+    let landmarks = Landmarks::select_with_rng(&graph, 3, LandmarkStrategy::Avoid, &mut rng);
+    
+    let path = std::env::temp_dir().join(format!("alt_landmarks_{}.json", std::process::id()));
+    landmarks.save(&path).unwrap();
+    let loaded = Landmarks::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    
+    assert_eq!(loaded.landmarks(), landmarks.landmarks());
+    for &target in graph.nodes() {
+        assert_eq!(loaded.lower_bound(0, target), landmarks.lower_bound(0, target));
+        assert_eq!(alt_query(&graph, &loaded, 0, target), alt_query(&graph, &landmarks, 0, target));
+    }
+}
+
+#[test]
+fn test_contraction_hierarchy_matches_dijkstra() {
+    use sssp_algos::algorithms::ContractionHierarchy;
+    
+    let mut rng = test_rng();
+    let graph = generate_random_graph_with_rng(70, 0.05, &mut rng);
+    let hierarchy = ContractionHierarchy::build(&graph);
+    
+    for &source in graph.nodes().iter().take(5) {
+        let expected = dijkstra(&graph, source);
+        
+        for &target in graph.nodes() {
+            assert_eq!(hierarchy.distance(source, target), Some(expected[&target]));
+            
+            let (distance, path) = hierarchy.path(source, target).unwrap();
+            assert_eq!(distance, expected[&target]);
+            assert_eq!(path.first(), Some(&source));
+            assert_eq!(path.last(), Some(&target));
+            
+            // The unpacked path only uses original edges and has the reported cost
+            let cost: usize = path.windows(2)
+                .map(|pair| graph.neighbors(pair[0]).iter()
+                    .filter(|&&(to, _)| to == pair[1])
+                    .map(|&(_, weight)| weight)
+                    .min()
+                    .expect("path uses a missing edge"))
+                .sum();
+            assert_eq!(cost, distance);
+        }
+    }
+    
+    let directed = ContractionHierarchy::build(&create_test_graph());
+    assert_eq!(directed.path(0, 6), Some((6, vec![0, 4, 5, 6])));
+    assert_eq!(directed.distance(6, 0), None);
+}
+
+#[test]
+fn test_multi_source_algorithms() {
+    use sssp_algos::algorithms::{multi_source_bfs, multi_source_bmssp, multi_source_dijkstra};
+    
+    let mut rng = test_rng();
+    let graph = generate_random_graph_with_rng(60, 0.05, &mut rng);
+    let sources = [(3, 0), (17, 25), (42, 5), (3, 10)];
+    
+    // Reference: a virtual super-source with an edge of weight `offset` to each source
+    let mut extended = graph.clone();
+    let super_source = usize::MAX - 1;
+    for &(source, offset) in &sources {
+        extended.add_edge(super_source, source, offset);
+    }
+    let expected = dijkstra(&extended, super_source);
+    
+    let per_source: Vec<_> = sources.iter().map(|&(source, _)| (source, dijkstra(&graph, source))).collect();
+    
+    for result in [
+        multi_source_dijkstra(&graph, &sources),
+        multi_source_bfs(&graph, &sources),
+        multi_source_bmssp(&graph, &sources),
+    ] {
+        assert_eq!(result.distances.len(), graph.node_count());
+        
+        for &node in graph.nodes() {
+            assert_eq!(result.distance(node), expected[&node], "Distance mismatch for node {}", node);
+            
+            // The reported nearest source must actually realize the distance
+            let nearest = result.nearest_source(node).unwrap();
+            let (_, from_nearest) = per_source.iter().find(|(source, _)| *source == nearest).unwrap();
+            let offset = sources.iter().filter(|&&(s, _)| s == nearest).map(|&(_, o)| o).min().unwrap();
+            assert_eq!(offset + from_nearest[&node], result.distance(node));
+        }
+    }
+}
+
+#[test]
+fn test_bfs_matches_dijkstra_on_random_graph() {
+    let mut rng = test_rng();
+    let graph = generate_random_graph_with_rng(80, 0.1, &mut rng);
+    
+    assert_eq!(bfs(&graph, 0), dijkstra(&graph, 0));
+    assert_eq!(sssp_algos::algorithms::bmssp(&graph, 0), dijkstra(&graph, 0));
+}
+
+#[test]
+fn test_bmssp_matches_dijkstra() {
+    use sssp_algos::algorithms::bmssp;
+    
+    let mut rng = test_rng();
+    for &(size, density) in &[(2, 0.5), (30, 0.1), (300, 0.01), (2000, 0.002)] {
+        for _ in 0..3 {
+            let graph = generate_random_graph_with_rng(size, density, &mut rng);
+            assert_eq!(bmssp(&graph, 0), dijkstra(&graph, 0), "Mismatch on a {}-node graph", size);
+        }
+    }
+    
+    let graph = create_test_graph();
+    assert_eq!(bmssp(&graph, 2), dijkstra(&graph, 2));
+}
+
+#[test]
+fn test_bounded_msssp() {
+    use sssp_algos::algorithms::{bounded_msssp, bounded_msssp_at_level, multi_source_dijkstra};
+    
+    let mut rng = test_rng();
+    let graph = generate_random_graph_with_rng(500, 0.01, &mut rng);
+    let sources = [(0, 0), (250, 40)];
+    let expected = multi_source_dijkstra(&graph, &sources);
+    let bound = 120;
+    
+    let (new_bound, settled, distances) = bounded_msssp(&graph, &sources, bound);
+    assert_eq!(new_bound, bound);
+    for &node in graph.nodes() {
+        assert_eq!(settled.contains(&node), expected.distance(node) < bound, "node {}", node);
+    }
+    for &node in &settled {
+        assert_eq!(distances[&node], expected.distance(node));
+    }
+    
+    // A low level may stop early, but everything below B' is still settled exactly
+    let (partial_bound, settled, distances) = bounded_msssp_at_level(&graph, &sources, usize::MAX, 1);
+    assert!(partial_bound < usize::MAX);
+    for &node in graph.nodes() {
+        assert_eq!(settled.contains(&node), expected.distance(node) < partial_bound, "node {}", node);
+    }
+    for &node in &settled {
+        assert_eq!(distances[&node], expected.distance(node));
+    }
+    
+    // Sources at or beyond the bound are not part of U
+    let far_sources = [(0, 0), (250, bound)];
+    let expected = multi_source_dijkstra(&graph, &far_sources);
+    let (_, settled, _) = bounded_msssp(&graph, &far_sources, bound);
+    for &node in graph.nodes() {
+        assert_eq!(settled.contains(&node), expected.distance(node) < bound, "node {}", node);
+    }
+    for level in 0..3 {
+        let (new_bound, settled, _) = bounded_msssp_at_level(&graph, &[(13, 4)], 1, level);
+        assert_eq!((new_bound, settled.len()), (1, 0));
+        let (_, settled, _) = bounded_msssp_at_level(&graph, &[(0, 0), (1, 0)], 0, level);
+        assert!(settled.is_empty());
+    }
+    let (_, settled, _) = bounded_msssp(&graph, &[(13, 4)], 1);
+    assert!(settled.is_empty());
+}
+
+// Every simple path from `node` to `target`, found by exhaustive DFS
+fn all_simple_paths(graph: &Graph, node: usize, target: usize, path: &mut Vec<usize>, cost: usize, out: &mut Vec<(usize, Vec<usize>)>) {
+    if node == target {
+        out.push((cost, path.clone()));
+        return;
+    }
+    for &(next, weight) in graph.neighbors(node) {
+        if !path.contains(&next) {
+            path.push(next);
+            all_simple_paths(graph, next, target, path, cost + weight, out);
+            path.pop();
+        }
+    }
+}
+
+#[test]
+fn test_yen_k_shortest_paths() {
+    use sssp_algos::algorithms::yen_k_shortest_paths;
+    
+    let graph = create_grid_graph(4, 3);
+    let mut expected = Vec::new();
+    all_simple_paths(&graph, 0, 11, &mut vec![0], 0, &mut expected);
+    expected.sort();
+    
+    let paths = yen_k_shortest_paths(&graph, 0, 11, 15);
+    assert_eq!(paths.len(), 15);
+    
+    let costs: Vec<usize> = paths.iter().map(|(cost, _)| *cost).collect();
+    let expected_costs: Vec<usize> = expected.iter().take(15).map(|(cost, _)| *cost).collect();
+    assert_eq!(costs, expected_costs);
+    
+    for (cost, path) in &paths {
+        assert!(expected.contains(&(*cost, path.clone())), "{:?} is not a simple path", path);
+    }
+    
+    // Asking for more paths than exist returns all of them
+    let graph = create_test_graph();
+    assert_eq!(yen_k_shortest_paths(&graph, 0, 6, 10), vec![
+        (6, vec![0, 4, 5, 6]),
+        (7, vec![0, 1, 2, 6]),
+        (12, vec![0, 1, 5, 6]),
+    ]);
+    assert!(yen_k_shortest_paths(&graph, 6, 0, 3).is_empty());
+}
+
+// Cost of `path` using the cheapest edge between consecutive nodes
+fn path_cost(graph: &Graph, path: &[usize]) -> Option<usize> {
+    path.windows(2).map(|pair| graph.edge_weight(pair[0], pair[1])).sum()
+}
+
+// Every walk from `node` to `target` costing at most `bound` (weights must be positive)
+fn all_walks(graph: &Graph, node: usize, target: usize, bound: usize, path: &mut Vec<usize>, cost: usize, out: &mut Vec<(usize, Vec<usize>)>) {
+    if node == target {
+        out.push((cost, path.clone()));
+    }
+    for &(next, weight) in graph.neighbors(node) {
+        if cost + weight <= bound {
+            path.push(next);
+            all_walks(graph, next, target, bound, path, cost + weight, out);
+            path.pop();
+        }
+    }
+}
+
+#[test]
+fn test_path_enumerator_walks_in_cost_order() {
+    use sssp_algos::algorithms::PathEnumerator;
+    
+    let graph = create_grid_graph(3, 3);
+    let bound = 80;
+    let mut expected = Vec::new();
+    all_walks(&graph, 0, 8, bound, &mut vec![0], 0, &mut expected);
+    expected.sort();
+    
+    let mut walks: Vec<_> = PathEnumerator::new(&graph, 0, 8).with_max_cost(bound).collect();
+    assert!(walks.windows(2).all(|pair| pair[0].0 <= pair[1].0), "walks must come out in cost order");
+    walks.sort();
+    assert_eq!(walks, expected);
+    
+    // Without a bound the enumeration is lazy and unbounded on cyclic graphs
+    assert_eq!(PathEnumerator::new(&graph, 0, 8).take(500).count(), 500);
+}
+
+#[test]
+fn test_paths_within_factor() {
+    use sssp_algos::algorithms::{paths_within_factor, yen_k_shortest_paths};
+    
+    let graph = create_grid_graph(4, 4);
+    let optimum = dijkstra(&graph, 0)[&15];
+    let bound = (optimum as f64 * 1.25).floor() as usize;
+    
+    let mut expected = Vec::new();
+    all_simple_paths(&graph, 0, 15, &mut vec![0], 0, &mut expected);
+    expected.retain(|(cost, _)| *cost <= bound);
+    expected.sort();
+    
+    let paths: Vec<_> = paths_within_factor(&graph, 0, 15, 0.25, true).collect();
+    assert!(paths.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    assert_eq!(paths[0].0, optimum);
+    
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(sorted, expected);
+    
+    // The loopless enumeration agrees with Yen's algorithm on the costs
+    let yen: Vec<usize> = yen_k_shortest_paths(&graph, 0, 15, paths.len()).into_iter().map(|(c, _)| c).collect();
+    assert_eq!(yen, paths.iter().map(|(c, _)| *c).collect::<Vec<_>>());
+    
+    assert_eq!(paths_within_factor(&create_test_graph(), 6, 0, 0.5, false).count(), 0);
+}
+
+#[test]
+fn test_path_enumerator_multigraph() {
+    use sssp_algos::algorithms::{paths_within_factor, yen_k_shortest_paths, PathEnumerator};
+    
+    let mut graph = Graph::new();
+    graph.add_edge(1, 0, 1);
+    graph.add_edge(1, 0, 2);
+    graph.add_edge(0, 2, 5);
+    graph.add_edge(0, 2, 3);
+    graph.add_edge(0, 2, 3);
+    let paths: Vec<_> = PathEnumerator::new(&graph, 1, 2).simple_paths().collect();
+    assert_eq!(paths, vec![(4, vec![1, 0, 2])]);
+    assert_eq!(paths, yen_k_shortest_paths(&graph, 1, 2, 5));
+    
+    // Heavier copies of every edge change nothing: each walk appears once,
+    // at the cost of its cheapest edges
+    let grid = create_grid_graph(3, 3);
+    let mut doubled = grid.clone();
+    for &node in grid.nodes() {
+        for &(neighbor, weight) in grid.neighbors(node) {
+            doubled.add_edge(node, neighbor, weight + 1);
+        }
+    }
+    let walks: Vec<_> = PathEnumerator::new(&doubled, 0, 8).with_max_cost(60).collect();
+    assert_eq!(walks, PathEnumerator::new(&grid, 0, 8).with_max_cost(60).collect::<Vec<_>>());
+    assert!(walks.iter().all(|(cost, path)| path_cost(&doubled, path) == Some(*cost)));
+    assert_eq!(
+        paths_within_factor(&doubled, 0, 8, 0.5, true).collect::<Vec<_>>(),
+        paths_within_factor(&grid, 0, 8, 0.5, true).collect::<Vec<_>>(),
+    );
+}
+
+// Copy of `graph` with every weight mapped through `f`
+fn map_weights(graph: &Graph, f: impl Fn(usize) -> usize) -> Graph {
+    let mut mapped = Graph::new();
+    for &node in graph.nodes() {
+        mapped.add_node(node);
+        for &(neighbor, weight) in graph.neighbors(node) {
+            mapped.add_edge(node, neighbor, f(weight));
+        }
+    }
+    mapped
+}
+
+#[test]
+fn test_registry_selects_fast_paths() {
+    use sssp_algos::algorithms::Solver;
+    
+    let mut rng = test_rng();
+    let graph = generate_random_graph_with_rng(100, 0.05, &mut rng);
+    let unit = map_weights(&graph, |_| 1);
+    let zero_one = map_weights(&graph, |w| w % 2);
+    let small = map_weights(&graph, |w| w % 7);
+    let large = map_weights(&graph, |w| w * 1000);
+    
+    assert_eq!(Solver::select(&unit), Solver::UnitBfs);
+    assert_eq!(Solver::select(&zero_one), Solver::ZeroOneBfs);
+    assert_eq!(Solver::select(&small), Solver::Dial);
+    assert_eq!(Solver::select(&large), Solver::Dijkstra);
+    assert_eq!(Solver::select(&Graph::new()), Solver::UnitBfs);
+    
+    for graph in [&unit, &zero_one, &small, &large] {
+        let expected = dijkstra(graph, 0);
+        assert_eq!(sssp_algos::algorithms::solve(graph, 0), expected);
+        
+        for solver in Solver::ALL {
+            if solver.supports(graph) {
+                assert_eq!(solver.run(graph, 0), expected, "{} disagrees with Dijkstra", solver.name());
+            }
+        }
+    }
+    
+    assert!(!Solver::UnitBfs.supports(&zero_one));
+    assert!(!Solver::ZeroOneBfs.supports(&small));
+}
+
+#[test]
+fn test_zero_weight_edges_all_algorithms() {
+    use sssp_algos::algorithms::{
+        alt_query, bmssp, multi_source_bfs, multi_source_bmssp, multi_source_dijkstra, paths_within_factor,
+        yen_k_shortest_paths, ContractionHierarchy, LandmarkStrategy, Landmarks, PathEnumerator, Solver,
+    };
+    
+    let mut rng = test_rng();
+    let fixture = create_zero_weight_graph();
+    let expected = dijkstra(&fixture, 0);
+    assert_eq!(expected[&3], 5);
+    assert_eq!(expected[&4], 5);
+    assert_eq!(expected[&7], 7);
+    
+    // The zero-weight cycle 1 -> 2 -> 3 -> 1 must not stall the enumeration
+    let walks: Vec<_> = PathEnumerator::new(&fixture, 0, 7).take(3).collect();
+    assert_eq!(walks.len(), 3);
+    assert!(walks.iter().all(|(cost, path)| *cost == 7 && path_cost(&fixture, path) == Some(7)));
+    assert_eq!(paths_within_factor(&fixture, 0, 7, 0.0, false).take(3).count(), 3);
+    for &target in fixture.nodes() {
+        let distance = Some(expected[&target]).filter(|&d| d != usize::MAX);
+        assert_eq!(PathEnumerator::new(&fixture, 0, target).next().map(|(d, _)| d), distance);
+    }
+    let mut simple: Vec<_> = paths_within_factor(&fixture, 0, 7, 0.0, true).collect();
+    simple.sort();
+    let mut optimal = yen_k_shortest_paths(&fixture, 0, 7, 10);
+    optimal.retain(|(cost, _)| *cost == 7);
+    assert_eq!(simple, optimal);
+    
+    let mut graphs = vec![fixture];
+    for &zero_fraction in &[0.3, 0.8] {
+        for _ in 0..3 {
+            graphs.push(generate_zero_weight_graph_with_rng(120, 0.04, zero_fraction, &mut rng));
+        }
+    }
+    
+    for graph in &graphs {
+        let expected = dijkstra(graph, 0);
+        let reverse = graph.reversed();
+        let hierarchy = ContractionHierarchy::build(graph);
+        let landmarks = Landmarks::select_with_rng(graph, 3, LandmarkStrategy::Avoid, &mut rng);
+        
+        assert_eq!(bfs(graph, 0), expected);
+        assert_eq!(bmssp(graph, 0), expected);
+        for solver in Solver::ALL {
+            if solver.supports(graph) {
+                assert_eq!(solver.run(graph, 0), expected, "{} disagrees with Dijkstra", solver.name());
+            }
+        }
+        
+        let sources = [(0, 0), (1, 3)];
+        let multi = multi_source_dijkstra(graph, &sources);
+        assert_eq!(multi_source_bfs(graph, &sources).distances, multi.distances);
+        assert_eq!(multi_source_bmssp(graph, &sources).distances, multi.distances);
+        
+        for &target in graph.nodes() {
+            let distance = Some(expected[&target]).filter(|&d| d != usize::MAX);
+            
+            assert_eq!(bidirectional_dijkstra_with_reverse(graph, &reverse, 0, target).map(|(d, _)| d), distance);
+            assert_eq!(astar(graph, 0, target, &|_| 0).map(|(d, _)| d), distance);
+            assert_eq!(alt_query(graph, &landmarks, 0, target).map(|(d, _)| d), distance);
+            assert_eq!(hierarchy.distance(0, target), distance);
+            assert_eq!(hierarchy.path(0, target).map(|(d, path)| (d, *path.last().unwrap())), distance.map(|d| (d, target)));
+            assert_eq!(yen_k_shortest_paths(graph, 0, target, 1).first().map(|(d, _)| *d), distance);
+        }
+    }
+}
+
+#[test]
+fn test_dynamic_sssp_insertions_and_decreases() {
+    use rand::Rng;
+    use sssp_algos::algorithms::DynamicSssp;
+    
+    let mut rng = test_rng();
+    let mut dynamic = DynamicSssp::new(generate_random_graph_with_rng(80, 0.03, &mut rng), 0);
+    
+    for step in 0..200 {
+        let from = rng.gen_range(0..85);
+        let to = rng.gen_range(0..85);
+        
+        if step % 2 == 0 {
+            dynamic.insert_edge(from, to, rng.gen_range(0..=60));
+        } else if let Some(&(neighbor, weight)) = dynamic.graph().neighbors(from).first() {
+            dynamic.decrease_weight(from, neighbor, weight / 2);
+        }
+        
+        assert!(dynamic.verify().is_empty(), "Mismatch after step {}: {:?}", step, dynamic.verify());
+    }
+    
+    // Path reconstruction from the maintained tree stays valid
+    for &node in dynamic.graph().nodes() {
+        if let Some(path) = dynamic.tree().path_to(node) {
+            let cost: usize = path.windows(2).map(|p| dynamic.graph().edge_weight(p[0], p[1]).unwrap()).sum();
+            assert_eq!(cost, dynamic.distance(node));
+        }
+    }
+}
+
+#[test]
+fn test_dynamic_sssp_touches_only_affected_nodes() {
+    use sssp_algos::algorithms::DynamicSssp;
+    
+    let mut dynamic = DynamicSssp::new(create_test_graph(), 0);
+    
+    // A shortcut to 6 improves only node 6
+    assert_eq!(dynamic.insert_edge(0, 6, 1), 1);
+    assert_eq!(dynamic.distance(6), 1);
+    // Raising a weight through decrease_weight is ignored
+    assert_eq!(dynamic.decrease_weight(0, 1, 10), 0);
+    // Cheaper 0 -> 1 improves 1, 2 and 3 (5 and 6 are already cheaper)
+    assert_eq!(dynamic.decrease_weight(0, 1, 1), 3);
+    assert_eq!(dynamic.distance(3), 5);
+    assert!(dynamic.verify().is_empty());
+}
+
+#[test]
+fn test_dynamic_sssp_deletions_and_increases_property() {
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    use sssp_algos::algorithms::DynamicSssp;
+    
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut dynamic = DynamicSssp::new(generate_zero_weight_graph_with_rng(40, 0.08, 0.2, &mut rng), 0);
+        
+        for step in 0..60 {
+            let from = rng.gen_range(0..40);
+            let edges = dynamic.graph().neighbors(from).to_vec();
+            
+            match (rng.gen_range(0..4), edges.first()) {
+                (0, _) => { dynamic.insert_edge(from, rng.gen_range(0..40), rng.gen_range(0..50)); }
+                (1, Some(&(to, weight))) => { dynamic.decrease_weight(from, to, weight / 2); }
+                (2, Some(&(to, weight))) => { dynamic.increase_weight(from, to, weight * 2 + 5); }
+                (_, Some(&(to, _))) => { dynamic.delete_edge(from, to); }
+                _ => {}
+            }
+            
+            let mismatches = dynamic.verify();
+            assert!(mismatches.is_empty(), "seed {} step {}: {:?}", seed, step, mismatches);
+        }
+    }
+}
+
+#[test]
+fn test_dynamic_sssp_repairs_only_subtree() {
+    use sssp_algos::algorithms::DynamicSssp;
+    
+    let mut dynamic = DynamicSssp::new(create_test_graph(), 0);
+    
+    // 1 -> 5 is not a tree edge, so nothing changes
+    assert_eq!(dynamic.delete_edge(1, 5), 0);
+    // Slower 0 -> 4 changes 4, 5 (still behind 4) and 6 (now via 2)
+    assert_eq!(dynamic.increase_weight(0, 4, 10), 3);
+    assert_eq!(dynamic.distance(4), 10);
+    assert_eq!(dynamic.distance(5), 11);
+    assert_eq!(dynamic.distance(6), 7);
+    // Cutting 0 -> 1 disconnects 1, 2 and 3 and pushes 6 back behind 5
+    assert_eq!(dynamic.delete_edge(0, 1), 4);
+    assert_eq!(dynamic.distance(3), usize::MAX);
+    assert_eq!(dynamic.distance(6), 14);
+    assert_eq!(dynamic.tree().path_to(3), None);
+    assert!(dynamic.verify().is_empty());
+}
+
+#[test]
+fn test_query_workspace_reuse() {
+    use sssp_algos::algorithms::{bfs_with_workspace, bmssp_with_workspace, dijkstra_with_workspace, QueryWorkspace};
+    
+    let mut rng = test_rng();
+    let graph = generate_zero_weight_graph_with_rng(150, 0.03, 0.2, &mut rng);
+    let mut workspace = QueryWorkspace::new(&graph);
+    
+    // Alternate algorithms and sources so stale values from earlier queries would show up
+    for &source in graph.nodes().iter().step_by(10) {
+        let expected = dijkstra(&graph, source);
+        
+        dijkstra_with_workspace(&graph, source, &mut workspace);
+        assert_eq!(workspace.distances(), expected);
+        
+        bfs_with_workspace(&graph, source, &mut workspace);
+        assert_eq!(workspace.distances(), expected);
+        
+        bmssp_with_workspace(&graph, source, &mut workspace);
+        assert_eq!(workspace.distances(), expected);
+        assert_eq!(workspace.reached().count(), expected.values().filter(|&&d| d != usize::MAX).count());
+    }
+    
+    // Only the part reachable from 6 is reported after a query from 6
+    let graph = create_test_graph();
+    let mut workspace = QueryWorkspace::new(&graph);
+    dijkstra_with_workspace(&graph, 0, &mut workspace);
+    dijkstra_with_workspace(&graph, 6, &mut workspace);
+    assert_eq!(workspace.reached().collect::<Vec<_>>(), vec![(6, 0)]);
+    assert_eq!(workspace.distance(0), usize::MAX);
+}
+
+#[test]
+fn test_batch_queries() {
+    use sssp_algos::algorithms::{batch_distances, batch_for_each};
+    
+    let mut rng = test_rng();
+    let graph = generate_random_graph_with_rng(200, 0.02, &mut rng);
+    let sources: Vec<usize> = (0..200).step_by(7).collect();
+    let targets = [3, 50, 199, 1000];
+    
+    // Full matrix, one row per source over all nodes
+    let matrix = batch_distances(&graph, &sources, None, 4);
+    assert_eq!(matrix.sources(), &sources[..]);
+    assert_eq!(matrix.targets(), &graph.nodes()[..]);
+    for (i, &source) in sources.iter().enumerate() {
+        let expected = dijkstra(&graph, source);
+        for (j, &target) in graph.nodes().iter().enumerate() {
+            assert_eq!(matrix.row(i)[j], expected[&target]);
+        }
+    }
+    
+    // Restricted targets (1000 is not in the graph) with the default thread count
+    let matrix = batch_distances(&graph, &sources, Some(&targets), 0);
+    assert_eq!(matrix.distance(sources[2], 50), Some(dijkstra(&graph, sources[2])[&50]));
+    assert_eq!(matrix.distance(sources[2], 1000), Some(usize::MAX));
+    assert_eq!(matrix.distance(1, 50), None);
+    
+    // Streaming delivers every source exactly once
+    let mut seen = vec![0; sources.len()];
+    batch_for_each(&graph, &sources, Some(&targets), 3, |i, row| {
+        assert_eq!(row, matrix.row(i));
+        seen[i] += 1;
+    });
+    assert!(seen.iter().all(|&count| count == 1));
+    
+    assert_eq!(batch_distances(&graph, &[], None, 2).sources().len(), 0);
+}
+
+#[test]
+fn test_components_and_reachability() {
+    use sssp_algos::algorithms::{reachability, strongly_connected_components, weakly_connected_components};
+    
+    let mut rng = test_rng();
+    // Two cycles joined by a one-way edge, plus an isolated node
+    let mut graph = Graph::new();
+    for i in 0..6 {
+        graph.add_node(i);
+    }
+    graph.add_edge(0, 1, 1);
+    graph.add_edge(1, 2, 1);
+    graph.add_edge(2, 0, 1);
+    graph.add_edge(2, 3, 1);
+    graph.add_edge(3, 4, 1);
+    graph.add_edge(4, 3, 1);
+    
+    let mut sccs: Vec<Vec<usize>> = strongly_connected_components(&graph).into_iter()
+        .map(|mut component| { component.sort(); component })
+        .collect();
+    // Reverse topological order: the sink {3, 4} before {0, 1, 2}
+    let sink = sccs.iter().position(|c| c == &vec![3, 4]).unwrap();
+    let head = sccs.iter().position(|c| c == &vec![0, 1, 2]).unwrap();
+    assert!(sink < head);
+    sccs.sort();
+    assert_eq!(sccs, vec![vec![0, 1, 2], vec![3, 4], vec![5]]);
+    
+    let mut wccs: Vec<Vec<usize>> = weakly_connected_components(&graph).into_iter()
+        .map(|mut component| { component.sort(); component })
+        .collect();
+    wccs.sort();
+    assert_eq!(wccs, vec![vec![0, 1, 2, 3, 4], vec![5]]);
+    
+    let report = reachability(&graph, 3);
+    assert_eq!((report.reached, report.total), (2, 6));
+    let mut unreached = report.unreached.clone();
+    unreached.sort();
+    assert_eq!(unreached, vec![0, 1, 2, 5]);
+    assert!((report.fraction() - 2.0 / 6.0).abs() < 1e-9);
+    assert_eq!(reachability(&graph, 99).reached, 0);
+    
+    // The generator promises strong connectivity, and every algorithm must
+    // reach exactly the reachable nodes
+    let graph = generate_random_graph_with_rng(300, 0.01, &mut rng);
+    assert_eq!(strongly_connected_components(&graph).len(), 1);
+    let report = reachability(&graph, 0);
+    assert!(report.is_complete());
+    let finite = |d: &std::collections::HashMap<usize, usize>| d.values().filter(|&&v| v != usize::MAX).count();
+    assert_eq!(finite(&dijkstra(&graph, 0)), report.reached);
+    assert_eq!(finite(&bfs(&graph, 0)), report.reached);
+    
+    // A long path must not overflow the stack
+    let mut path = Graph::new();
+    for i in 0..200_000 {
+        path.add_edge(i, i + 1, 1);
+    }
+    assert_eq!(strongly_connected_components(&path).len(), 200_001);
+}
+
+#[test]
+fn test_seeded_generators_are_reproducible() {
+    use sssp_algos::{generate_random_graph_seeded, generate_zero_weight_graph_seeded};
+    
+    let edges = |graph: &Graph| -> Vec<(usize, Vec<(usize, usize)>)> {
+        graph.nodes().iter().map(|&node| (node, graph.neighbors(node).to_vec())).collect()
+    };
+    
+    let a = generate_random_graph_seeded(200, 0.05, 42);
+    let b = generate_random_graph_seeded(200, 0.05, 42);
+    let c = generate_random_graph_seeded(200, 0.05, 43);
+    assert_eq!(edges(&a), edges(&b));
+    assert_ne!(edges(&a), edges(&c));
+    
+    let a = generate_zero_weight_graph_seeded(100, 0.05, 0.5, 7);
+    let b = generate_zero_weight_graph_with_rng(100, 0.05, 0.5, &mut StdRng::seed_from_u64(7));
+    assert_eq!(edges(&a), edges(&b));
+}
+
+#[test]
+fn test_gnm_generator() {
+    use std::collections::HashSet;
+    use sssp_algos::generators::{edges_for_average_degree, generate_gnm_graph_seeded, generate_gnm_graph_with_rng, WeightDistribution};
+    
+    let mut rng = test_rng();
+    let weights = WeightDistribution::default();
+    
+    let distinct = |graph: &Graph| -> HashSet<(usize, usize)> {
+        graph.nodes().iter()
+            .flat_map(|&from| graph.neighbors(from).iter().map(move |&(to, _)| (from, to)))
+            .collect()
+    };
+    
+    // Sparse directed: exact count, no duplicates or self-loops
+    let graph = generate_gnm_graph_with_rng(10_000, 30_000, true, &weights, &mut rng);
+    assert_eq!(graph.node_count(), 10_000);
+    assert_eq!(graph.edge_count(), 30_000);
+    assert_eq!(distinct(&graph).len(), 30_000);
+    assert!(distinct(&graph).iter().all(|&(from, to)| from != to));
+    
+    // Undirected edges are stored both ways
+    let edges = edges_for_average_degree(1000, 3.0, false);
+    assert_eq!(edges, 1500);
+    let graph = generate_gnm_graph_with_rng(1000, edges, false, &weights, &mut rng);
+    assert_eq!(graph.edge_count(), 3000);
+    for &node in graph.nodes() {
+        for &(neighbor, weight) in graph.neighbors(node) {
+            assert_eq!(graph.edge_weight(neighbor, node), Some(weight));
+        }
+    }
+    
+    // Dense requests take the complement path; too many edges gives the complete graph
+    let graph = generate_gnm_graph_with_rng(30, 800, true, &weights, &mut rng);
+    assert_eq!(distinct(&graph).len(), 800);
+    assert_eq!(generate_gnm_graph_with_rng(20, 1000, false, &weights, &mut rng).edge_count(), 20 * 19);
+    assert_eq!(generate_gnm_graph_with_rng(1, 5, true, &weights, &mut rng).edge_count(), 0);
+    
+    assert_eq!(distinct(&generate_gnm_graph_seeded(500, 2000, true, &weights, 9)), distinct(&generate_gnm_graph_seeded(500, 2000, true, &weights, 9)));
+}
+
+#[test]
+fn test_grid_generators() {
+    use sssp_algos::algorithms::EuclideanHeuristic;
+    use sssp_algos::generators::{generate_grid_3d_graph_with_rng, generate_grid_graph_with_rng, GridOptions};
+    
+    let mut rng = test_rng();
+    
+    // Plain 4-neighbour grid: 2·w·h − w − h undirected edges
+    let graph = generate_grid_graph_with_rng(20, 10, &GridOptions::default(), &mut rng);
+    assert_eq!(graph.node_count(), 200);
+    assert_eq!(graph.edge_count(), 2 * (2 * 20 * 10 - 20 - 10));
+    assert_eq!(graph.position(13), Some((13.0, 0.0)));
+    assert_eq!(dijkstra(&graph, 0)[&199], 10 * (19 + 9));
+    
+    // Diagonals add two edges per inner square; a diagonal costs ceil(10·√2)
+    let options = GridOptions { diagonals: true, ..GridOptions::default() };
+    let graph = generate_grid_graph_with_rng(20, 10, &options, &mut rng);
+    assert_eq!(graph.edge_count(), 2 * (2 * 20 * 10 - 20 - 10 + 2 * 19 * 9));
+    assert_eq!(graph.edge_weight(0, 21), Some(15));
+    
+    // 3D: 3·w·h·d − w·h − h·d − w·d axis edges
+    let graph = generate_grid_3d_graph_with_rng(5, 4, 3, &GridOptions::default(), &mut rng);
+    assert_eq!(graph.node_count(), 60);
+    assert_eq!(graph.edge_count(), 2 * (3 * 60 - 20 - 12 - 15));
+    assert_eq!(graph.position(0), None);
+    let graph = generate_grid_3d_graph_with_rng(3, 3, 3, &GridOptions { diagonals: true, ..GridOptions::default() }, &mut rng);
+    assert_eq!(graph.neighbors(13).len(), 26);
+    
+    // Obstacles remove cells; perturbed weights stay within bounds and A* stays exact
+    let options = GridOptions { diagonals: true, obstacle_fraction: 0.2, base_weight: 100, perturbation: 0.3 };
+    let graph = generate_grid_graph_with_rng(30, 30, &options, &mut rng);
+    assert!(graph.node_count() < 900 && graph.node_count() > 600);
+    for &node in graph.nodes() {
+        for &(_, weight) in graph.neighbors(node) {
+            assert!((70..=184).contains(&weight), "weight {} out of range", weight);
+        }
+    }
+    let source = graph.nodes()[0];
+    let expected = dijkstra(&graph, source);
+    for &target in graph.nodes().iter().step_by(37) {
+        let heuristic = EuclideanHeuristic::new(&graph, target, options.heuristic_scale()).unwrap();
+        let expected = Some(expected[&target]).filter(|&d| d != usize::MAX);
+        assert_eq!(astar(&graph, source, target, &heuristic).map(|(d, _)| d), expected);
+    }
+}
+
+#[test]
+fn test_geometric_graph_generator() {
+    use sssp_algos::algorithms::EuclideanHeuristic;
+    use sssp_algos::generators::{generate_geometric_graph_with_rng, radius_for_average_degree};
+    
+    let mut rng = test_rng();
+    let radius = radius_for_average_degree(2000, 8.0);
+    let scale = 1000.0;
+    let graph = generate_geometric_graph_with_rng(2000, radius, scale, &mut rng);
+    
+    assert_eq!(graph.node_count(), 2000);
+    let average = graph.edge_count() as f64 / 2000.0;
+    assert!(average > 5.0 && average < 9.0, "average degree {}", average);
+    
+    // Every edge is shorter than the radius and weighs its rounded-up length
+    for &node in graph.nodes() {
+        let (x, y) = graph.position(node).unwrap();
+        for &(neighbor, weight) in graph.neighbors(node) {
+            let (nx, ny) = graph.position(neighbor).unwrap();
+            let length = ((x - nx).powi(2) + (y - ny).powi(2)).sqrt();
+            assert!(length < radius * scale + 1e-6);
+            assert!((length.ceil() as i64 - weight as i64).abs() <= 1);
+        }
+    }
+    
+    // Brute force agrees on the edge count
+    let positions: Vec<(f64, f64)> = (0..2000).map(|node| graph.position(node).unwrap()).collect();
+    let mut pairs = 0;
+    for i in 0..2000 {
+        for j in i + 1..2000 {
+            let (a, b) = (positions[i], positions[j]);
+            if ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt() < radius * scale - 1e-6 {
+                pairs += 1;
+            }
+        }
+    }
+    assert!((graph.edge_count() / 2).abs_diff(pairs) <= 2);
+    
+    // Radii far below 1/sqrt(n) get wider cells but the same edges
+    let sparse = generate_geometric_graph_with_rng(400, 0.02, 1.0, &mut rng);
+    let mut expected_edges = 0;
+    for i in 0..400 {
+        for j in i + 1..400 {
+            let (a, b) = (sparse.position(i).unwrap(), sparse.position(j).unwrap());
+            if ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt() < 0.02 {
+                expected_edges += 1;
+                assert!(sparse.edge_weight(i, j).is_some(), "missing edge {} - {}", i, j);
+            }
+        }
+    }
+    assert_eq!(sparse.edge_count(), 2 * expected_edges);
+    let tiny = generate_geometric_graph_with_rng(100_000, 1e-5, 1.0, &mut rng);
+    assert_eq!(tiny.node_count(), 100_000);
+    
+    let expected = dijkstra(&graph, 0);
+    for target in (0..2000).step_by(97) {
+        let heuristic = EuclideanHeuristic::new(&graph, target, 1.0).unwrap();
+        let expected = Some(expected[&target]).filter(|&d| d != usize::MAX);
+        assert_eq!(astar(&graph, 0, target, &heuristic).map(|(d, _)| d), expected);
+    }
+}
+
+#[test]
+fn test_scale_free_generators() {
+    use sssp_algos::algorithms::bmssp;
+    use sssp_algos::generators::{
+        generate_barabasi_albert_graph_with_rng, generate_rmat_graph_with_rng, RmatParams, WeightDistribution,
+    };
+    
+    let mut rng = test_rng();
+    
+    // Barabási–Albert: clique on m + 1 nodes, then m edges per new node
+    let weights = WeightDistribution::Uniform { min: 5, max: 9 };
+    let graph = generate_barabasi_albert_graph_with_rng(3000, 3, &weights, &mut rng);
+    assert_eq!(graph.node_count(), 3000);
+    assert_eq!(graph.edge_count(), 2 * (6 + 3 * (3000 - 4)));
+    let max_degree = graph.nodes().iter().map(|&node| graph.neighbors(node).len()).max().unwrap();
+    assert!(max_degree > 50, "no hubs: max degree {}", max_degree);
+    for &node in graph.nodes() {
+        assert!(graph.neighbors(node).len() >= 3);
+        assert!(graph.neighbors(node).iter().all(|&(_, weight)| (5..=9).contains(&weight)));
+    }
+    assert_eq!(bmssp(&graph, 0), dijkstra(&graph, 0));
+    
+    // R-MAT: heavy-tailed degrees, no self-loops, constant weights honoured
+    let graph = generate_rmat_graph_with_rng(11, 8, &RmatParams::GRAPH500, &WeightDistribution::Constant(7), &mut rng);
+    assert_eq!(graph.node_count(), 2048);
+    assert!(graph.edge_count() <= 8 * 2048 && graph.edge_count() > 7 * 2048);
+    let mut degrees: Vec<usize> = graph.nodes().iter().map(|&node| graph.neighbors(node).len()).collect();
+    degrees.sort_unstable();
+    assert!(degrees[2047] > 20 * degrees[1024].max(1), "degrees are not skewed");
+    for &node in graph.nodes() {
+        assert!(graph.neighbors(node).iter().all(|&(to, weight)| to != node && weight == 7));
+    }
+    let source = (0..2048).max_by_key(|&node| graph.neighbors(node).len()).unwrap();
+    assert_eq!(bmssp(&graph, source), dijkstra(&graph, source));
+    
+    // Uniform parameters give an Erdős–Rényi-like graph without hubs
+    let uniform = RmatParams { a: 1.0, b: 1.0, c: 1.0, d: 1.0 };
+    let graph = generate_rmat_graph_with_rng(10, 4, &uniform, &WeightDistribution::default(), &mut rng);
+    let max_degree = graph.nodes().iter().map(|&node| graph.neighbors(node).len()).max().unwrap();
+    assert!(max_degree < 20);
+}
+
+#[test]
+#[should_panic(expected = "R-MAT scale 64 is too large")]
+fn test_rmat_rejects_oversized_scale() {
+    use sssp_algos::generators::{generate_rmat_graph_seeded, RmatParams, WeightDistribution};
+    
+    generate_rmat_graph_seeded(64, 1, &RmatParams::GRAPH500, &WeightDistribution::default(), 1);
+}
+
+#[test]
+fn test_weight_distributions() {
+    use std::collections::HashSet;
+    use sssp_algos::{generate_weighted_random_graph, generate_weighted_random_graph_with_rng};
+    use sssp_algos::generators::{generate_gnm_graph_with_rng, WeightDistribution};
+    
+    let mut rng = test_rng();
+    let samples = |distribution: &WeightDistribution, rng: &mut StdRng| -> Vec<usize> {
+        (0..20_000).map(|_| distribution.sample(rng)).collect()
+    };
+    let mean = |values: &[usize]| values.iter().sum::<usize>() as f64 / values.len() as f64;
+    
+    let uniform = samples(&WeightDistribution::Uniform { min: 3, max: 8 }, &mut rng);
+    assert!(uniform.iter().all(|w| (3..=8).contains(w)));
+    assert!((mean(&uniform) - 5.5).abs() < 0.1);
+    
+    assert!(samples(&WeightDistribution::Constant(4), &mut rng).iter().all(|&w| w == 4));
+    
+    let exponential = WeightDistribution::Exponential { mean: 50.0 };
+    let values = samples(&exponential, &mut rng);
+    assert!((mean(&values) - 50.0).abs() < 2.0);
+    // Median of an exponential is mean · ln 2
+    let below = values.iter().filter(|&&w| (w as f64) < 50.0 * 2f64.ln()).count();
+    assert!((below as f64 / 20_000.0 - 0.5).abs() < 0.03);
+    
+    let log_normal = WeightDistribution::LogNormal { mu: 3.0, sigma: 1.0 };
+    let values = samples(&log_normal, &mut rng);
+    assert!((mean(&values) / log_normal.mean() - 1.0).abs() < 0.1);
+    assert!(values.iter().max().unwrap() > &(20 * 20));
+    
+    let bimodal = WeightDistribution::Bimodal { light: (1, 5), heavy: (1000, 1100), heavy_fraction: 0.1 };
+    let values = samples(&bimodal, &mut rng);
+    let heavy = values.iter().filter(|&&w| w >= 1000).count();
+    assert!(values.iter().all(|&w| (1..=5).contains(&w) || (1000..=1100).contains(&w)));
+    assert!((heavy as f64 / 20_000.0 - 0.1).abs() < 0.02);
+    assert!((mean(&values) - bimodal.mean()).abs() < 10.0);
+    
+    // Floating-point weights: rounding a narrow range gives heavy ties, scaling removes them
+    let float = WeightDistribution::UniformFloat { min: 1.0, max: 2.0 };
+    let distinct = |values: Vec<usize>| values.into_iter().collect::<HashSet<_>>().len();
+    assert!(distinct(samples(&float, &mut rng)) <= 2);
+    let scaled = WeightDistribution::Scaled(Box::new(float.clone()), 1e6);
+    let values = samples(&scaled, &mut rng);
+    assert!(values.iter().all(|&w| (1_000_000..=2_000_000).contains(&w)));
+    assert!(distinct(values) > 19_000);
+    assert_eq!(scaled.mean(), 1.5e6);
+    
+    // Generators draw from the distribution they are given
+    let graph = generate_gnm_graph_with_rng(500, 2000, true, &bimodal, &mut rng);
+    let weights: Vec<usize> = graph.nodes().iter().flat_map(|&n| graph.neighbors(n).iter().map(|&(_, w)| w)).collect();
+    assert!(weights.iter().all(|&w| w <= 5 || w >= 1000));
+    let graph = generate_weighted_random_graph_with_rng(300, 0.02, &exponential, &mut rng);
+    assert_eq!(dijkstra(&graph, 0), sssp_algos::algorithms::bmssp(&graph, 0));
+    let graph = generate_weighted_random_graph(100, 0.05, &WeightDistribution::Constant(7));
+    assert_eq!(graph.node_count(), 100);
+    assert!(graph.nodes().iter().all(|&n| graph.neighbors(n).iter().all(|&(_, w)| w == 7)));
+}
+
+#[test]
+fn test_adversarial_families() {
+    use sssp_algos::algorithms::{bmssp, ContractionHierarchy, Solver};
+    use sssp_algos::generators::{
+        generate_chain_with_shortcuts, generate_decrease_key_graph, generate_layered_graph_with_rng, generate_tie_grid,
+        WeightDistribution,
+    };
+    
+    let mut rng = test_rng();
+    
+    // Decrease-key family: every distance is reached along the chain
+    let graph = generate_decrease_key_graph(100, 100);
+    assert_eq!(graph.edge_count(), 100 * 99 / 2);
+    let distances = dijkstra(&graph, 0);
+    assert!((0..100).all(|node| distances[&node] == node));
+    let sparse = generate_decrease_key_graph(300, 8);
+    assert_eq!(sparse.edge_count(), (0..300).map(|i: usize| 8.min(299 - i)).sum::<usize>());
+    
+    // Chain with shortcuts: the shortcuts never help
+    let chain = generate_chain_with_shortcuts(300, 6);
+    assert_eq!(chain.edge_weight(10, 42), Some(33));
+    assert!((0..300).all(|node| dijkstra(&chain, 0)[&node] == node));
+    
+    // Layered graph: width · width edges between consecutive layers
+    let layered = generate_layered_graph_with_rng(8, 12, &WeightDistribution::Uniform { min: 1, max: 20 }, &mut rng);
+    assert_eq!(layered.node_count(), 1 + 8 * 12);
+    assert_eq!(layered.edge_count(), 12 + 7 * 12 * 12);
+    
+    // Tie grid: distance is the Manhattan distance
+    let ties = generate_tie_grid(20, 10);
+    assert_eq!(dijkstra(&ties, 0)[&(9 * 20 + 19)], 19 + 9);
+    
+    for graph in [&graph, &sparse, &chain, &layered, &ties] {
+        let expected = dijkstra(graph, 0);
+        assert_eq!(bfs(graph, 0), expected);
+        assert_eq!(bmssp(graph, 0), expected);
+        for solver in Solver::ALL.iter().filter(|solver| solver.supports(graph)) {
+            assert_eq!(solver.run(graph, 0), expected, "{} differs", solver.name());
+        }
+    }
+    
+    // Contraction is only affordable on small or grid-like instances
+    for graph in [&generate_chain_with_shortcuts(80, 4), &ties] {
+        let last = *graph.nodes().last().unwrap();
+        let hierarchy = ContractionHierarchy::build(graph);
+        assert_eq!(hierarchy.distance(0, last), Some(dijkstra(graph, 0)[&last]));
+    }
+}
+
+#[test]
+fn test_dimacs_round_trip() {
+    use sssp_algos::io::{load_gr, read_gr, read_p2p, read_ss, save_gr, write_gr, write_p2p, write_ss};
+    
+    let text = "c 9th DIMACS challenge sample\n\
+                p sp 4 5\n\
+                c arcs\n\
+                a 1 2 7\n\
+                a 1 3 2\n\
+                a 3 2 1\n\
+                a 2 4 3\n\
+                \n\
+                a 3 4 9\n";
+    let graph = read_gr(text.as_bytes()).unwrap();
+    assert_eq!(graph.node_count(), 4);
+    assert_eq!(graph.edge_count(), 5);
+    assert_eq!(graph.edge_weight(0, 1), Some(7));
+    assert_eq!(dijkstra(&graph, 0)[&3], 6);
+    
+    // Writing and reading back preserves every arc
+    let mut buffer = Vec::new();
+    write_gr(&graph, &mut buffer).unwrap();
+    let again = read_gr(buffer.as_slice()).unwrap();
+    for &node in graph.nodes() {
+        let mut expected = graph.neighbors(node).to_vec();
+        let mut actual = again.neighbors(node).to_vec();
+        expected.sort();
+        actual.sort();
+        assert_eq!(actual, expected);
+    }
+    
+    // Through a file, with a random graph
+    let mut rng = test_rng();
+    let random = generate_random_graph_with_rng(200, 0.02, &mut rng);
+    let path = std::env::temp_dir().join(format!("dimacs_{}.gr", std::process::id()));
+    save_gr(&random, &path).unwrap();
+    let loaded = load_gr(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.edge_count(), random.edge_count());
+    assert_eq!(dijkstra(&loaded, 5), dijkstra(&random, 5));
+    
+    // Query files
+    let mut buffer = Vec::new();
+    write_ss(&[0, 41, 7], &mut buffer).unwrap();
+    assert_eq!(String::from_utf8(buffer.clone()).unwrap(), "p aux sp ss 3\ns 1\ns 42\ns 8\n");
+    assert_eq!(read_ss(buffer.as_slice()).unwrap(), vec![0, 41, 7]);
+    
+    let mut buffer = Vec::new();
+    write_p2p(&[(0, 3), (2, 1)], &mut buffer).unwrap();
+    assert_eq!(read_p2p(buffer.as_slice()).unwrap(), vec![(0, 3), (2, 1)]);
+    assert_eq!(read_p2p("c q\np aux sp p2p 1\nq 5 9\n".as_bytes()).unwrap(), vec![(4, 8)]);
+}
+
+#[test]
+fn test_dimacs_parse_errors() {
+    use sssp_algos::io::{read_gr, read_p2p, read_ss, ParseError};
+    
+    let error_at = |result: std::io::Result<Graph>| -> (usize, String) {
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        let parse = ParseError::from_io(&error).expect("not a parse error");
+        (parse.line, parse.message.clone())
+    };
+    
+    let cases = [
+        ("a 1 2 3\n", 1, "arc before the problem line"),
+        ("p sp 2 1\na 1 3 4\n", 2, "node id 3 out of range 1..=2"),
+        ("p sp 2 1\na 0 1 4\n", 2, "node ids are 1-based, found 0"),
+        ("p sp 2 1\nc\na 1 2 x\n", 3, "invalid arc weight `x`"),
+        ("p sp 2 1\na 1 2\n", 2, "missing arc weight"),
+        ("p sp 2 1\na 1 2 3 4\n", 2, "unexpected field `4`"),
+        ("p sp 2 2\na 1 2 3\n", 2, "expected 2 arcs, found 1"),
+        ("p max 2 1\n", 1, "expected `sp`, found `max`"),
+        ("p sp 2 0\np sp 2 0\n", 2, "duplicate problem line"),
+        ("p sp 2 0\nx 1\n", 2, "unknown line type `x`"),
+        ("c only comments\n", 1, "missing problem line `p sp <nodes> <arcs>`"),
+    ];
+    for (input, line, message) in cases {
+        assert_eq!(error_at(read_gr(input.as_bytes())), (line, message.to_string()), "input {:?}", input);
+    }
+    
+    let error = read_ss("p aux sp ss 2\ns 1\n".as_bytes()).unwrap_err();
+    assert_eq!(ParseError::from_io(&error).unwrap().to_string(), "line 2: expected 2 queries, found 1");
+    let error = read_p2p("p aux sp ss 1\nq 1 2\n".as_bytes()).unwrap_err();
+    assert_eq!(ParseError::from_io(&error).unwrap().line, 1);
+    
+    // Plain I/O failures are not parse errors
+    let missing = sssp_algos::io::load_gr("/nonexistent/graph.gr").unwrap_err();
+    assert!(ParseError::from_io(&missing).is_none());
+}
+
+#[test]
+fn test_edge_list_loader() {
+    use sssp_algos::io::{load_edge_list, read_edge_list, EdgeListOptions, ParseError};
+    
+    // SNAP style: whitespace, `#` comments, no weights, arbitrary integer ids
+    let snap = "# Directed graph: example.txt\n# FromNodeId\tToNodeId\n30\t1000\n1000\t7\n\n30\t7\n";
+    let (graph, ids) = read_edge_list(snap.as_bytes(), &EdgeListOptions::default()).unwrap();
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(ids.len(), 3);
+    assert_eq!((ids.id("30"), ids.id("1000"), ids.id("7")), (Some(0), Some(1), Some(2)));
+    assert_eq!(ids.label(1), Some("1000"));
+    assert_eq!(graph.edge_weight(0, 1), Some(1));
+    assert_eq!(graph.edge_weight(1, 0), None);
+    
+    // Keeping the original ids
+    let options = EdgeListOptions { remap: false, ..EdgeListOptions::default() };
+    let (graph, ids) = read_edge_list(snap.as_bytes(), &options).unwrap();
+    assert_eq!(graph.edge_weight(1000, 7), Some(1));
+    assert_eq!(ids.id("1000"), Some(1000));
+    
+    // CSV with string labels, weights in the third column, undirected
+    let csv = "% road segments\nsource, target, length\n";
+    let options = EdgeListOptions {
+        delimiter: Some(','),
+        comment_prefix: "%".to_string(),
+        weight_column: Some(2),
+        directed: false,
+        ..EdgeListOptions::default()
+    };
+    let error = read_edge_list(csv.as_bytes(), &options).unwrap_err();
+    assert_eq!(ParseError::from_io(&error).unwrap().to_string(), "line 2: invalid weight `length`");
+    
+    let csv = "% road segments\nA, B, 4\nB,C,2\nC , A, 9\n";
+    let (graph, ids) = read_edge_list(csv.as_bytes(), &options).unwrap();
+    let (a, c) = (ids.id("A").unwrap(), ids.id("C").unwrap());
+    assert_eq!(graph.edge_count(), 6);
+    assert_eq!(dijkstra(&graph, a)[&c], 6);
+    assert_eq!(dijkstra(&graph, c)[&a], 6);
+    
+    // Fractional weights kept as fixed point; default weight and column order
+    let tsv = "0.25\tx\ty\n1.5\ty\tz\n";
+    let options = EdgeListOptions {
+        delimiter: Some('\t'),
+        source_column: 1,
+        target_column: 2,
+        weight_column: Some(0),
+        weight_scale: 100.0,
+        ..EdgeListOptions::default()
+    };
+    let (graph, ids) = read_edge_list(tsv.as_bytes(), &options).unwrap();
+    assert_eq!(graph.edge_weight(ids.id("x").unwrap(), ids.id("y").unwrap()), Some(25));
+    assert_eq!(graph.edge_weight(ids.id("y").unwrap(), ids.id("z").unwrap()), Some(150));
+    let options = EdgeListOptions { default_weight: 3, ..EdgeListOptions::default() };
+    let (graph, _) = read_edge_list("a b\n".as_bytes(), &options).unwrap();
+    assert_eq!(graph.edge_weight(0, 1), Some(3));
+    
+    // Errors carry line numbers
+    let bad = [
+        ("1 2\n3\n", EdgeListOptions::default(), "line 2: missing target in column 2"),
+        ("1 x\n", EdgeListOptions { remap: false, ..EdgeListOptions::default() }, "line 1: invalid node id `x`"),
+        ("1 2 -4\n", EdgeListOptions { weight_column: Some(2), ..EdgeListOptions::default() }, "line 1: invalid weight `-4`"),
+        ("a b 5\n", EdgeListOptions { weight_column: Some(2), weight_scale: 1e30, ..EdgeListOptions::default() }, "line 1: weight `5` out of range"),
+        ("a b 1\nb c 99999999999999999999999\n", EdgeListOptions { weight_column: Some(2), ..EdgeListOptions::default() }, "line 2: weight `99999999999999999999999` out of range"),
+        ("a b 1\n", EdgeListOptions { weight_column: Some(2), weight_scale: f64::INFINITY, ..EdgeListOptions::default() }, "line 1: weight `1` out of range"),
+    ];
+    for (input, options, message) in bad {
+        let error = read_edge_list(input.as_bytes(), &options).unwrap_err();
+        assert_eq!(ParseError::from_io(&error).unwrap().to_string(), message);
+    }
+    
+    // From a file
+    let path = std::env::temp_dir().join(format!("edges_{}.txt", std::process::id()));
+    std::fs::write(&path, snap).unwrap();
+    let (graph, _) = load_edge_list(&path, &EdgeListOptions::default()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(graph.edge_count(), 3);
+}
+
+#[test]
+fn test_graph_serialization_round_trip() {
+    use sssp_algos::generators::{generate_geometric_graph_with_rng, radius_for_average_degree};
+    
+    let mut rng = test_rng();
+    
+    // Parallel edges, zero weights, isolated nodes and positions all survive
+    let mut fixture = create_zero_weight_graph();
+    fixture.add_edge(0, 1, 2);
+    fixture.add_node(42);
+    fixture.set_position(3, 1.5, -2.25);
+    let geometric = generate_geometric_graph_with_rng(300, radius_for_average_degree(300, 6.0), 100.0, &mut rng);
+    let random = generate_random_graph_with_rng(300, 0.02, &mut rng);
+    
+    for graph in [&fixture, &geometric, &random] {
+        let json = graph.to_json().unwrap();
+        let from_json = Graph::from_json(&json).unwrap();
+        assert_eq!(&from_json, graph);
+        assert_eq!(from_json.nodes(), graph.nodes());
+        
+        let bytes = graph.to_binary().unwrap();
+        let from_binary = Graph::from_binary(&bytes).unwrap();
+        assert_eq!(&from_binary, graph);
+        
+        // Serialization is deterministic
+        assert_eq!(from_json.to_json().unwrap(), json);
+        assert_eq!(dijkstra(&from_binary, 0), dijkstra(graph, 0));
+    }
+    
+    let directory = std::env::temp_dir();
+    let json_path = directory.join(format!("graph_{}.json", std::process::id()));
+    let binary_path = directory.join(format!("graph_{}.bin", std::process::id()));
+    random.save_json(&json_path).unwrap();
+    random.save_binary(&binary_path).unwrap();
+    assert_eq!(Graph::load_json(&json_path).unwrap(), random);
+    assert_eq!(Graph::load_binary(&binary_path).unwrap(), random);
+    
+    // Corrupt input is an error, not a panic
+    std::fs::write(&binary_path, [1, 2, 3]).unwrap();
+    assert_eq!(Graph::load_binary(&binary_path).unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    std::fs::remove_file(&json_path).unwrap();
+    std::fs::remove_file(&binary_path).unwrap();
+    assert!(Graph::from_json("{\"nodes\": [1]}").is_err());
+    
+    // Payloads that do not describe a consistent graph are rejected
+    for bad in [
+        r#"{"nodes": [0, 1], "adjacency": [[[1, 5]], [[0, 2]], [[0, 9]]], "positions": []}"#,
+        r#"{"nodes": [0, 1, 2], "adjacency": [], "positions": []}"#,
+        r#"{"nodes": [0, 0], "adjacency": [[], []], "positions": []}"#,
+        r#"{"nodes": [0, 1], "adjacency": [[[7, 1]], []], "positions": []}"#,
+        r#"{"nodes": [0], "adjacency": [[]], "positions": [[3, 0.5, 0.5]]}"#,
+    ] {
+        assert!(Graph::from_json(bad).is_err(), "{}", bad);
+    }
+    // The binary form has the same shape: node list, adjacency lists, positions
+    let positions: Vec<(usize, f64, f64)> = Vec::new();
+    let short = bincode::serialize(&(vec![0usize, 1, 2], vec![vec![(1usize, 5usize)]], &positions)).unwrap();
+    assert!(Graph::from_binary(&short).is_err());
+    let valid = bincode::serialize(&(vec![0usize, 1], vec![vec![(1usize, 5usize)], vec![]], &positions)).unwrap();
+    assert_eq!(Graph::from_binary(&valid).unwrap().edge_weight(0, 1), Some(5));
+}
+
+#[test]
+fn test_result_serialization_round_trip() {
+    use sssp_algos::algorithms::{
+        batch_distances, dijkstra_tree, multi_source_dijkstra, reachability, DistanceMatrix, MultiSourceResult,
+        ReachabilityReport, ShortestPathTree,
+    };
+    
+    let graph = create_test_graph();
+    
+    let tree = dijkstra_tree(&graph, 0);
+    let json = serde_json::to_string(&tree).unwrap();
+    let decoded: ShortestPathTree = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, tree);
+    assert_eq!(decoded.path_to(6), tree.path_to(6));
+    let decoded: ShortestPathTree = bincode::deserialize(&bincode::serialize(&tree).unwrap()).unwrap();
+    assert_eq!(decoded, tree);
+    
+    let result = multi_source_dijkstra(&graph, &[(0, 0), (2, 1)]);
+    let decoded: MultiSourceResult = serde_json::from_str(&serde_json::to_string(&result).unwrap()).unwrap();
+    assert_eq!(decoded, result);
+    
+    let matrix = batch_distances(&graph, &[0, 1], None, 1);
+    let decoded: DistanceMatrix = bincode::deserialize(&bincode::serialize(&matrix).unwrap()).unwrap();
+    assert_eq!(decoded, matrix);
+    assert_eq!(decoded.distance(0, 6), matrix.distance(0, 6));
+    
+    let report = reachability(&graph, 1);
+    let decoded: ReachabilityReport = serde_json::from_str(&serde_json::to_string(&report).unwrap()).unwrap();
+    assert_eq!(decoded, report);
+}
+
+#[test]
+fn test_csr_format() {
+    use sssp_algos::io::{load_csr, save_csr, write_csr, CsrGraph};
+    use std::io::ErrorKind;
+    
+    let mut rng = test_rng();
+    let mut graph = generate_random_graph_with_rng(400, 0.02, &mut rng);
+    graph.add_node(1000);
+    graph.add_edge(3, 3, 0);
+    
+    let mut bytes = Vec::new();
+    write_csr(&graph, &mut bytes).unwrap();
+    let csr = CsrGraph::read(&bytes[..]).unwrap();
+    assert_eq!(csr.node_count(), graph.node_count());
+    assert_eq!(csr.edge_count(), graph.edge_count());
+    assert_eq!(csr.to_graph(), graph);
+    assert_eq!(csr.to_graph().nodes(), graph.nodes());
+    
+    // Dijkstra on the CSR arrays agrees with the adjacency-list version
+    let source = csr.index_of(0).unwrap();
+    let expected = dijkstra(&graph, 0);
+    for (index, distance) in csr.dijkstra(source).into_iter().enumerate() {
+        let id = csr.node_ids()[index] as usize;
+        assert_eq!(distance, expected.get(&id).copied().unwrap_or(usize::MAX), "node {}", id);
+    }
+    
+    let path = std::env::temp_dir().join(format!("graph_{}.csr", std::process::id()));
+    save_csr(&graph, &path).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), bytes);
+    let mapped = CsrGraph::open(&path).unwrap();
+    assert_eq!(mapped.offsets(), csr.offsets());
+    assert_eq!(mapped.targets(), csr.targets());
+    assert_eq!(mapped.weights(), csr.weights());
+    assert_eq!(load_csr(&path).unwrap(), graph);
+    std::fs::remove_file(&path).unwrap();
+    
+    // Damaged files are rejected
+    let mut corrupt = bytes.clone();
+    *corrupt.last_mut().unwrap() ^= 1;
+    assert_eq!(CsrGraph::read(&corrupt[..]).err().unwrap().kind(), ErrorKind::InvalidData);
+    let mut newer = bytes.clone();
+    newer[8] = 2;
+    assert!(CsrGraph::read(&newer[..]).err().unwrap().to_string().contains("version 2"));
+    assert_eq!(CsrGraph::read(&bytes[..bytes.len() - 4]).err().unwrap().kind(), ErrorKind::InvalidData);
+    assert_eq!(CsrGraph::read(&b"p sp 1 0\n"[..]).err().unwrap().kind(), ErrorKind::InvalidData);
+    
+    // Weights are stored in 32 bits
+    let mut heavy = Graph::new();
+    heavy.add_edge(0, 1, u32::MAX as usize + 1);
+    assert_eq!(write_csr(&heavy, Vec::new()).unwrap_err().kind(), ErrorKind::InvalidInput);
+    
+    let empty = CsrGraph::read(&{
+        let mut bytes = Vec::new();
+        write_csr(&Graph::new(), &mut bytes).unwrap();
+        bytes
+    }[..]).unwrap();
+    assert_eq!(empty.node_count(), 0);
+    assert!(empty.dijkstra(0).is_empty());
+}